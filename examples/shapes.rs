@@ -0,0 +1,19 @@
+use engine::core::{Application, EngineError, Game};
+use engine::graphics::{Color, Frame};
+
+struct Shapes;
+
+impl Game for Shapes {
+    fn render(&mut self, frame: &mut Frame, _alpha: f32) {
+        frame.clear(Color::rgb(0.2, 0.3, 0.8)); // Blue
+
+        frame.draw_circle(90.0, 32, Color::BLUE);
+    }
+}
+
+fn main() -> Result<(), EngineError> {
+    tracing_subscriber::fmt::init();
+
+    let app = Application::new("Shapes", 800, 600, Box::new(Shapes));
+    app.run()
+}
@@ -1,82 +1,268 @@
+use crate::core::{EngineError, Game, Time};
 use crate::graphics::Renderer;
-use crate::input::Input;
+use crate::input::{CursorMode, Input, InputEvent, Recording};
 use pollster::block_on;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tracing::info;
-use winit::dpi::LogicalSize;
+use tracing::{error, info, warn};
+use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
 use winit::event_loop::ControlFlow;
 use winit::{
     application::ApplicationHandler,
-    event::WindowEvent,
+    event::{DeviceEvent, DeviceId, WindowEvent},
     event_loop::{ActiveEventLoop, EventLoop},
-    window::{Window, WindowId},
+    window::{CursorGrabMode, Window, WindowId},
 };
 
+/// How often an idle [`FramePacing::Reactive`] application checks for
+/// gamepad input.
+const GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// When `Application` renders frames. Every policy renders once per frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FramePacing {
+    /// One frame per display refresh, paced by presenting with vsync.
+    Vsync,
+    /// At most this many frames per second, sleeping in between. Vsync is off.
+    FpsCap(f64),
+    /// Only after input, a resize or [`Time::request_frame`], sleeping
+    /// otherwise. For tools and editors that don't animate constantly.
+    /// Gamepads are polled every 16 ms while asleep.
+    Reactive,
+}
+
 pub struct Application {
     window: Option<Arc<Window>>,
     renderer: Option<Renderer>,
+    game: Box<dyn Game>,
     input: Input,
     title: String,
     size: (u32, u32),
-    last_update: Instant,
-    accumulator: Duration,
-    fps: Duration,
+    time: Time,
+    error: Option<EngineError>,
+    record_path: Option<PathBuf>,
+    pause_when_unfocused: bool,
+    pacing: FramePacing,
+    /// Time between frames under [`FramePacing::FpsCap`].
+    frame_interval: Duration,
+    next_frame: Instant,
+    frame_requested: bool,
+    /// Whether the event loop went to sleep without a frame pending.
+    idle: bool,
 }
 
 impl Application {
-    pub fn new(title: impl Into<String>, width: u32, height: u32) -> Self {
+    pub fn new(title: impl Into<String>, width: u32, height: u32, game: Box<dyn Game>) -> Self {
         Self {
             window: None,
             renderer: None,
+            game,
             title: title.into(),
             size: (width, height),
-            last_update: Instant::now(),
-            accumulator: Duration::ZERO,
-            fps: Duration::from_secs_f64(1.0 / 60.0),
-            input: Input::new(),
+            time: Time::default(),
+            input: Self::create_input(),
+            error: None,
+            record_path: None,
+            pause_when_unfocused: false,
+            pacing: FramePacing::Vsync,
+            frame_interval: Duration::ZERO,
+            next_frame: Instant::now(),
+            frame_requested: true,
+            idle: false,
+        }
+    }
+
+    fn create_input() -> Input {
+        #[allow(unused_mut)]
+        let mut input = Input::new();
+        #[cfg(feature = "gilrs")]
+        if let Some(backend) = crate::input::GilrsBackend::new() {
+            input.gamepads.set_backend(backend);
         }
+        input
+    }
+
+    /// Records all input and writes it to `path` when the application exits.
+    pub fn record_input(mut self, path: impl Into<PathBuf>) -> Self {
+        self.input.start_recording();
+        self.record_path = Some(path.into());
+        self
+    }
+
+    /// Replays `recording` in place of live input, e.g. to reproduce a bug
+    /// report. See [`Input::start_playback`].
+    pub fn replay_input(mut self, recording: Recording) -> Self {
+        self.input.start_playback(recording);
+        self
     }
 
-    pub fn run(mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Stops running `fixed_update` while the window is unfocused. Time spent
+    /// unfocused is not caught up afterwards.
+    pub fn pause_when_unfocused(mut self, pause: bool) -> Self {
+        self.pause_when_unfocused = pause;
+        self
+    }
+
+    /// Limits how many fixed ticks a single frame may run to catch up after a
+    /// stall. Time beyond that is dropped so a slow frame can't cause an
+    /// ever-growing backlog.
+    pub fn max_catch_up_steps(mut self, steps: u32) -> Self {
+        self.time.set_max_catch_up_steps(steps);
+        self
+    }
+
+    /// Sets how many fixed ticks run per simulated second. Defaults to 60 and
+    /// is kept within 1..=10000.
+    pub fn tick_rate(mut self, tick_rate: f64) -> Self {
+        self.time.set_tick_rate(tick_rate);
+        self
+    }
+
+    /// Chooses when frames are rendered. Defaults to [`FramePacing::Vsync`].
+    /// An `FpsCap` that isn't a positive number or is too small to give a
+    /// frame interval is ignored with a warning.
+    pub fn frame_pacing(mut self, pacing: FramePacing) -> Self {
+        if let FramePacing::FpsCap(fps) = pacing {
+            match Duration::try_from_secs_f64(1.0 / fps) {
+                Ok(interval) if fps > 0.0 => self.frame_interval = interval,
+                _ => {
+                    warn!("Ignoring invalid frame rate cap {}", fps);
+                    return self;
+                }
+            }
+        }
+        self.pacing = pacing;
+        self
+    }
+
+    pub fn run(mut self) -> Result<(), EngineError> {
         let event_loop = EventLoop::new()?;
-        event_loop.set_control_flow(ControlFlow::Poll);
+        event_loop.set_control_flow(ControlFlow::Wait);
         event_loop.run_app(&mut self)?;
-        Ok(())
+
+        if let Some(path) = self.record_path.take()
+            && let Some(recording) = self.input.stop_recording()
+            && let Err(e) = recording.save(&path)
+        {
+            error!("{}", e);
+            self.error.get_or_insert(e);
+        }
+
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 
-    pub fn update(&mut self, dt: f64) {}
+    fn create_window(&mut self, event_loop: &ActiveEventLoop) -> Result<(), EngineError> {
+        let window_attributes = Window::default_attributes()
+            .with_title(&self.title)
+            .with_inner_size(LogicalSize::new(self.size.0, self.size.1));
 
-    pub fn render(&mut self) {
-        if let Some(renderer) = &mut self.renderer {
-            if let Some(mut frame) = renderer.begin_frame() {
-                frame.clear(crate::graphics::Color::rgb(0.2, 0.3, 0.8)); // Blue
+        let window = Arc::new(event_loop.create_window(window_attributes)?);
+        let renderer = block_on(Renderer::new(window.clone()))?;
+
+        self.input.mouse.set_scale_factor(window.scale_factor());
+        self.window = Some(window);
+        self.renderer = Some(renderer);
+        info!("Window created");
+        Ok(())
+    }
 
-                frame.draw_circle(0.15, 32, crate::graphics::Color::BLUE);
+    /// Applies window settings that game code requested through `Input`.
+    fn apply_input_requests(&mut self) {
+        let Some(window) = &self.window else {
+            return;
+        };
 
-                frame.present();
+        if let Some(request) = self.input.text.take_ime_request() {
+            window.set_ime_allowed(request.allowed);
+            if let Some((position, size)) = request.cursor_area {
+                window.set_ime_cursor_area(
+                    PhysicalPosition::new(position.x, position.y),
+                    PhysicalSize::new(size.x, size.y),
+                );
             }
         }
+
+        if let Some(request) = self.input.mouse.take_cursor_request() {
+            let result = match request.mode {
+                CursorMode::Normal => window.set_cursor_grab(CursorGrabMode::None),
+                CursorMode::Confined => window.set_cursor_grab(CursorGrabMode::Confined),
+                CursorMode::Locked => window
+                    .set_cursor_grab(CursorGrabMode::Locked)
+                    .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined)),
+            };
+            if let Err(e) = result {
+                warn!("Failed to grab cursor: {}", e);
+            }
+            window.set_cursor_visible(request.visible);
+        }
+    }
+
+    /// Runs one frame: fixed ticks, `update` and a single render.
+    fn frame(&mut self) {
+        self.input.poll_gamepads();
+
+        if std::mem::take(&mut self.idle) {
+            self.time.reset_frame_clock();
+        }
+        self.time.begin_frame();
+
+        // Recordings replay focus changes on ticks, so never pause during playback.
+        if self.pause_when_unfocused && !self.input.is_focused() && !self.input.is_playing_back() {
+            self.time.discard_pending_ticks();
+        }
+
+        while self.time.next_tick() {
+            self.input.begin_tick();
+            // Gesture timers run on ticks so recordings replay them exactly.
+            self.input.touches.update(self.time.delta_seconds());
+            self.game.fixed_update(&mut self.input, &mut self.time);
+            self.input.end_tick();
+        }
+
+        self.game.update(&mut self.input, &mut self.time);
+        self.apply_input_requests();
+
+        self.render();
+
+        // Edges and deltas have been seen by this frame's update and render.
+        self.input.end_frame();
+
+        if self.time.take_frame_request() {
+            self.frame_requested = true;
+        }
+    }
+
+    pub fn render(&mut self) {
+        if let Some(renderer) = &mut self.renderer
+            && let Some(mut frame) = renderer.begin_frame()
+        {
+            self.game.render(&mut frame, self.time.alpha());
+            frame.present();
+        }
     }
 }
 
 impl ApplicationHandler for Application {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_none() {
-            let window_attributes = Window::default_attributes()
-                .with_title(&self.title)
-                .with_inner_size(LogicalSize::new(self.size.0, self.size.1));
-
-            let window = match event_loop.create_window(window_attributes) {
-                Ok(window) => Arc::new(window),
-                Err(e) => panic!("Failed to create window: {}", e),
-            };
-
-            let renderer = block_on(Renderer::new(window.clone()));
+            if let Err(e) = self.create_window(event_loop) {
+                error!("{}", e);
+                self.error = Some(e);
+                event_loop.exit();
+                return;
+            }
 
-            self.window = Some(window);
-            self.renderer = Some(renderer);
-            info!("Window created");
+            if let Some(renderer) = &mut self.renderer {
+                renderer.set_vsync(!matches!(self.pacing, FramePacing::FpsCap(_)));
+                self.game.init(renderer);
+            }
+            if let Some(window) = &self.window {
+                window.request_redraw();
+            }
         }
     }
 
@@ -86,23 +272,34 @@ impl ApplicationHandler for Application {
         _window_id: WindowId,
         event: WindowEvent,
     ) {
+        self.game.on_event(&event);
+
+        if let Some(input_event) = InputEvent::from_window_event(&event) {
+            self.input.handle(input_event);
+            self.frame_requested = true;
+        }
+
         match event {
             WindowEvent::CloseRequested => {
                 info!("Close requested");
                 event_loop.exit();
             }
             WindowEvent::RedrawRequested => {
-                self.render();
-            }
-            WindowEvent::KeyboardInput { event, .. } => {
-                self.input.keyboard.process_event(&event);
+                self.frame();
+                if self.pacing == FramePacing::Vsync
+                    && let Some(window) = &self.window
+                {
+                    window.request_redraw();
+                }
             }
             WindowEvent::Resized(size) => {
                 if let Some(renderer) = &mut self.renderer {
                     renderer.resize(size);
                 }
+                self.frame_requested = true;
             }
-            WindowEvent::ScaleFactorChanged { .. } => {
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.input.mouse.set_scale_factor(scale_factor);
                 if let Some(renderer) = &mut self.renderer {
                     let size = self.window.as_ref().unwrap().inner_size();
                     renderer.resize(size);
@@ -112,23 +309,56 @@ impl ApplicationHandler for Application {
         }
     }
 
-    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
-        self.input.update();
-
-        let now = Instant::now();
-        let delta = now - self.last_update;
-        self.last_update = now;
-        self.accumulator += delta;
-
-        while self.accumulator >= self.fps {
-            self.update(self.fps.as_secs_f64());
-            self.accumulator -= self.fps;
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if let Some(input_event) = InputEvent::from_device_event(&event) {
+            self.input.handle(input_event);
+            self.frame_requested = true;
         }
+    }
 
-        self.render();
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(window) = &self.window else {
+            return;
+        };
 
-        if let Some(window) = &self.window {
-            window.request_redraw();
+        match self.pacing {
+            // Each frame requests the next; presenting blocks until vsync.
+            FramePacing::Vsync => {}
+            FramePacing::FpsCap(_) => {
+                let now = Instant::now();
+                if now >= self.next_frame {
+                    window.request_redraw();
+                    let interval = self.frame_interval;
+                    self.next_frame += interval;
+                    // Don't try to make up for frames missed while stalled.
+                    if self.next_frame < now {
+                        self.next_frame = now + interval;
+                    }
+                }
+                event_loop.set_control_flow(ControlFlow::WaitUntil(self.next_frame));
+            }
+            FramePacing::Reactive => {
+                // Gamepads don't wake the event loop, so poll them here and
+                // wake up regularly to check again while one may be connected.
+                if self.input.poll_gamepads() {
+                    self.frame_requested = true;
+                }
+                if std::mem::take(&mut self.frame_requested) {
+                    window.request_redraw();
+                } else {
+                    self.idle = true;
+                }
+                event_loop.set_control_flow(if self.input.gamepads.has_backend() {
+                    ControlFlow::WaitUntil(Instant::now() + GAMEPAD_POLL_INTERVAL)
+                } else {
+                    ControlFlow::Wait
+                });
+            }
         }
     }
 }
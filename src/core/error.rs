@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// Errors that can occur while creating or running the engine.
+#[derive(Debug)]
+pub enum EngineError {
+    EventLoop(winit::error::EventLoopError),
+    Window(winit::error::OsError),
+    Surface(wgpu::CreateSurfaceError),
+    UnsupportedSurface(String),
+    Adapter(wgpu::RequestAdapterError),
+    Device(wgpu::RequestDeviceError),
+    Shader(String),
+    Readback(String),
+    Asset(String),
+    Config(String),
+    Recording(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::EventLoop(e) => write!(f, "Failed to create event loop: {}", e),
+            EngineError::Window(e) => write!(f, "Failed to create window: {}", e),
+            EngineError::Surface(e) => write!(f, "Failed to create surface: {}", e),
+            EngineError::UnsupportedSurface(e) => write!(f, "Unsupported surface: {}", e),
+            EngineError::Adapter(e) => write!(f, "Failed to create adapter: {}", e),
+            EngineError::Device(e) => write!(f, "Failed to create device: {}", e),
+            EngineError::Shader(e) => write!(f, "Failed to compile shader: {}", e),
+            EngineError::Readback(e) => write!(f, "Failed to read back pixels: {}", e),
+            EngineError::Asset(e) => write!(f, "Failed to load asset: {}", e),
+            EngineError::Config(e) => write!(f, "Invalid config: {}", e),
+            EngineError::Recording(e) => write!(f, "Invalid input recording: {}", e),
+            EngineError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EngineError::EventLoop(e) => Some(e),
+            EngineError::Window(e) => Some(e),
+            EngineError::Surface(e) => Some(e),
+            EngineError::Adapter(e) => Some(e),
+            EngineError::Device(e) => Some(e),
+            EngineError::Io(e) => Some(e),
+            EngineError::UnsupportedSurface(_)
+            | EngineError::Shader(_)
+            | EngineError::Readback(_)
+            | EngineError::Asset(_)
+            | EngineError::Config(_)
+            | EngineError::Recording(_) => None,
+        }
+    }
+}
+
+impl From<winit::error::EventLoopError> for EngineError {
+    fn from(e: winit::error::EventLoopError) -> Self {
+        EngineError::EventLoop(e)
+    }
+}
+
+impl From<winit::error::OsError> for EngineError {
+    fn from(e: winit::error::OsError) -> Self {
+        EngineError::Window(e)
+    }
+}
+
+impl From<wgpu::CreateSurfaceError> for EngineError {
+    fn from(e: wgpu::CreateSurfaceError) -> Self {
+        EngineError::Surface(e)
+    }
+}
+
+impl From<wgpu::RequestAdapterError> for EngineError {
+    fn from(e: wgpu::RequestAdapterError) -> Self {
+        EngineError::Adapter(e)
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for EngineError {
+    fn from(e: wgpu::RequestDeviceError) -> Self {
+        EngineError::Device(e)
+    }
+}
+
+impl From<std::io::Error> for EngineError {
+    fn from(e: std::io::Error) -> Self {
+        EngineError::Io(e)
+    }
+}
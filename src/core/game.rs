@@ -0,0 +1,30 @@
+use super::Time;
+use crate::graphics::{Frame, Renderer};
+use crate::input::Input;
+use winit::event::WindowEvent;
+
+/// User code driven by [`Application`](super::Application).
+///
+/// Every hook has an empty default so a game only implements what it needs.
+pub trait Game {
+    /// Called once after the window and renderer have been created, e.g. to
+    /// load textures.
+    fn init(&mut self, _renderer: &mut Renderer) {}
+
+    /// Called zero or more times per frame. `time.delta()` is the fixed step.
+    fn fixed_update(&mut self, _input: &mut Input, _time: &mut Time) {}
+
+    /// Called once per frame. `time.delta()` is the simulated time since the
+    /// last frame.
+    fn update(&mut self, _input: &mut Input, _time: &mut Time) {}
+
+    /// Records draw calls for the current frame. The frame is presented afterwards.
+    ///
+    /// `alpha` in 0..1 is how far real time has advanced from the last fixed
+    /// tick towards the next, for interpolating state moved in
+    /// `fixed_update`; see [`InterpolatedTransform`](crate::graphics::InterpolatedTransform).
+    fn render(&mut self, _frame: &mut Frame, _alpha: f32) {}
+
+    /// Called for every window event before the engine handles it.
+    fn on_event(&mut self, _event: &WindowEvent) {}
+}
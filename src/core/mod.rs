@@ -1,3 +1,9 @@
 mod application;
+mod error;
+mod game;
+mod time;
 
-pub use application::Application;
+pub use application::{Application, FramePacing};
+pub use error::EngineError;
+pub use game::Game;
+pub use time::Time;
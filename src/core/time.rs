@@ -0,0 +1,253 @@
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Clocks and counters for the main loop, owned by
+/// [`Application`](super::Application) and passed to game hooks.
+///
+/// Real time follows the wall clock. Simulated time is real time multiplied
+/// by the time scale and stands still while paused; fixed ticks are taken
+/// from simulated time.
+#[derive(Debug)]
+pub struct Time {
+    last_frame: Option<Instant>,
+    real_delta: Duration,
+    real_elapsed: Duration,
+    delta: Duration,
+    elapsed: Duration,
+    fixed_step: Duration,
+    accumulator: Duration,
+    scale: f64,
+    paused: bool,
+    pending_steps: u32,
+    max_catch_up_steps: u32,
+    steps_this_frame: u32,
+    in_tick: bool,
+    started: bool,
+    frame_requested: bool,
+    frame: u64,
+    tick: u64,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TICK_RATE)
+    }
+}
+
+impl Time {
+    const DEFAULT_TICK_RATE: f64 = 60.0;
+    const MIN_TICK_RATE: f64 = 1.0;
+    const MAX_TICK_RATE: f64 = 10_000.0;
+    const MAX_TIME_SCALE: f64 = 1_000.0;
+
+    /// Creates clocks that run `tick_rate` fixed ticks per simulated second.
+    pub fn new(tick_rate: f64) -> Self {
+        Self {
+            last_frame: None,
+            real_delta: Duration::ZERO,
+            real_elapsed: Duration::ZERO,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            fixed_step: Self::step_for(tick_rate),
+            accumulator: Duration::ZERO,
+            scale: 1.0,
+            paused: false,
+            pending_steps: 0,
+            max_catch_up_steps: 5,
+            steps_this_frame: 0,
+            in_tick: false,
+            started: false,
+            frame_requested: false,
+            frame: 0,
+            tick: 0,
+        }
+    }
+
+    /// Starts a new frame, measuring real time since the previous one.
+    pub fn begin_frame(&mut self) {
+        let now = Instant::now();
+        let real_delta = self.last_frame.map_or(Duration::ZERO, |last| now - last);
+        self.last_frame = Some(now);
+        self.advance(real_delta);
+    }
+
+    /// Makes the next frame start from now, so time spent idle, e.g. waiting
+    /// for input under [`FramePacing::Reactive`](super::FramePacing::Reactive),
+    /// is neither simulated nor caught up.
+    pub fn reset_frame_clock(&mut self) {
+        self.last_frame = None;
+    }
+
+    /// Starts a new frame `real_delta` after the previous one. Used by
+    /// [`Time::begin_frame`]; call it directly to drive the clocks by hand.
+    pub fn advance(&mut self, real_delta: Duration) {
+        if self.started {
+            self.frame += 1;
+        }
+        self.started = true;
+        self.real_delta = real_delta;
+        self.real_elapsed += real_delta;
+        self.steps_this_frame = 0;
+
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            real_delta.mul_f64(self.scale)
+        };
+        self.elapsed += self.delta;
+        self.accumulator += self.delta;
+
+        if self.pending_steps > 0 {
+            self.accumulator += self.fixed_step * self.pending_steps;
+            self.elapsed += self.fixed_step * self.pending_steps;
+            self.pending_steps = 0;
+        }
+    }
+
+    /// Whether another fixed tick is due this frame. Call in a loop around
+    /// `fixed_update`; gives up after the catch-up limit and drops the
+    /// backlog so a stall can't snowball.
+    pub fn next_tick(&mut self) -> bool {
+        if self.in_tick {
+            self.in_tick = false;
+            self.tick += 1;
+        }
+        if self.accumulator < self.fixed_step {
+            return false;
+        }
+        if self.steps_this_frame == self.max_catch_up_steps {
+            let dropped = self.accumulator.as_nanos() / self.fixed_step.as_nanos();
+            debug!("Dropping {} fixed ticks to catch up", dropped);
+            self.accumulator -= self.fixed_step * dropped as u32;
+            return false;
+        }
+        self.accumulator -= self.fixed_step;
+        self.steps_this_frame += 1;
+        self.in_tick = true;
+        true
+    }
+
+    /// Throws away simulated time that hasn't been ticked yet.
+    pub fn discard_pending_ticks(&mut self) {
+        self.accumulator = Duration::ZERO;
+    }
+
+    /// Simulated time since the last frame, or the fixed step inside
+    /// `fixed_update`.
+    pub fn delta(&self) -> Duration {
+        if self.in_tick {
+            self.fixed_step
+        } else {
+            self.delta
+        }
+    }
+
+    pub fn delta_seconds(&self) -> f64 {
+        self.delta().as_secs_f64()
+    }
+
+    /// Wall-clock time since the last frame, unaffected by scale and pause.
+    pub fn real_delta(&self) -> Duration {
+        self.real_delta
+    }
+
+    /// Total simulated time.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Total wall-clock time since the first frame.
+    pub fn real_elapsed(&self) -> Duration {
+        self.real_elapsed
+    }
+
+    /// Index of the current frame, starting at 0.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Index of the current tick inside `fixed_update`, otherwise the number
+    /// of ticks run so far.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// How far simulated time has advanced from the last tick towards the
+    /// next, in 0..1.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f64() / self.fixed_step.as_secs_f64()).min(1.0) as f32
+    }
+
+    pub fn time_scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Speeds up (above 1) or slows down (below 1) simulated time. Kept
+    /// within 0..=1000; NaN is ignored.
+    pub fn set_time_scale(&mut self, scale: f64) {
+        if !scale.is_nan() {
+            self.scale = scale.clamp(0.0, Self::MAX_TIME_SCALE);
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Runs exactly one fixed tick next frame while paused, for debugging.
+    pub fn step(&mut self) {
+        if self.paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    /// Asks for another frame under
+    /// [`FramePacing::Reactive`](super::FramePacing::Reactive), e.g. while an
+    /// animation is playing. Other pacing policies render continuously anyway.
+    pub fn request_frame(&mut self) {
+        self.frame_requested = true;
+    }
+
+    pub fn take_frame_request(&mut self) -> bool {
+        std::mem::take(&mut self.frame_requested)
+    }
+
+    pub fn fixed_step(&self) -> Duration {
+        self.fixed_step
+    }
+
+    /// Fixed ticks per simulated second.
+    pub fn tick_rate(&self) -> f64 {
+        1.0 / self.fixed_step.as_secs_f64()
+    }
+
+    /// Kept within 1..=10000; NaN falls back to the default of 60.
+    pub fn set_tick_rate(&mut self, tick_rate: f64) {
+        self.fixed_step = Self::step_for(tick_rate);
+    }
+
+    fn step_for(tick_rate: f64) -> Duration {
+        let tick_rate = if tick_rate.is_nan() {
+            Self::DEFAULT_TICK_RATE
+        } else {
+            tick_rate.clamp(Self::MIN_TICK_RATE, Self::MAX_TICK_RATE)
+        };
+        Duration::from_secs_f64(1.0 / tick_rate)
+    }
+
+    pub fn max_catch_up_steps(&self) -> u32 {
+        self.max_catch_up_steps
+    }
+
+    pub fn set_max_catch_up_steps(&mut self, steps: u32) {
+        self.max_catch_up_steps = steps.max(1);
+    }
+}
@@ -0,0 +1,161 @@
+use super::geometry::Vertex;
+use super::texture::Texture;
+use super::transform::Transform2D;
+use glam::Vec2;
+use std::ops::Range;
+
+/// Number of GPU buffer pairs cycled through so a frame never writes into
+/// buffers the previous frames may still be reading.
+const RING_SIZE: usize = 3;
+const INITIAL_VERTICES: u64 = 4096;
+const INITIAL_INDICES: u64 = 8192;
+
+/// Counters for the last presented frame, useful for profiling.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Shapes recorded through the `Frame::draw_*` calls.
+    pub draw_calls: u32,
+    /// Indexed draws issued to the GPU.
+    pub batches: u32,
+    pub vertices: u32,
+    pub indices: u32,
+}
+
+/// A run of indices drawn with the same texture, or none for plain shapes.
+pub struct DrawRange {
+    pub indices: Range<u32>,
+    pub texture: Option<Texture>,
+}
+
+/// CPU-side geometry accumulated over a frame and submitted at `present`.
+#[derive(Default)]
+pub struct Batch {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub ranges: Vec<DrawRange>,
+}
+
+impl Batch {
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.ranges.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn push(&mut self, vertices: &[Vertex], indices: &[u32], texture: Option<&Texture>) {
+        let base = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(vertices);
+        self.push_indices(base, indices, texture);
+    }
+
+    /// Like [`Batch::push`], with `transform` applied to every vertex position.
+    pub fn push_transformed(
+        &mut self,
+        vertices: &[Vertex],
+        indices: &[u32],
+        transform: &Transform2D,
+        texture: Option<&Texture>,
+    ) {
+        let base = self.vertices.len() as u32;
+        let matrix = transform.matrix();
+        self.vertices.extend(vertices.iter().map(|vertex| {
+            let [x, y, z] = vertex.position;
+            let point = matrix.transform_point2(Vec2::new(x, y));
+            Vertex {
+                position: [point.x, point.y, z],
+                ..*vertex
+            }
+        }));
+        self.push_indices(base, indices, texture);
+    }
+
+    fn push_indices(&mut self, base: u32, indices: &[u32], texture: Option<&Texture>) {
+        let start = self.indices.len() as u32;
+        self.indices.extend(indices.iter().map(|i| base + i));
+
+        let end = self.indices.len() as u32;
+        match self.ranges.last_mut() {
+            Some(range) if range.texture.as_ref().map(Texture::id) == texture.map(Texture::id) => {
+                range.indices.end = end
+            }
+            _ => self.ranges.push(DrawRange {
+                indices: start..end,
+                texture: texture.cloned(),
+            }),
+        }
+    }
+}
+
+pub struct BufferSlot {
+    pub vertex: wgpu::Buffer,
+    pub index: wgpu::Buffer,
+}
+
+/// A ring of reusable vertex/index buffers that grow to fit the largest batch.
+pub struct BufferRing {
+    slots: Vec<BufferSlot>,
+    next: usize,
+}
+
+impl BufferRing {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let slots = (0..RING_SIZE)
+            .map(|_| BufferSlot {
+                vertex: Self::create_vertex_buffer(device, INITIAL_VERTICES),
+                index: Self::create_index_buffer(device, INITIAL_INDICES),
+            })
+            .collect();
+
+        Self { slots, next: 0 }
+    }
+
+    /// Uploads `batch` into the next slot of the ring, growing it if needed.
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        batch: &Batch,
+    ) -> &BufferSlot {
+        let slot = &mut self.slots[self.next];
+        self.next = (self.next + 1) % RING_SIZE;
+
+        let vertex_bytes: &[u8] = bytemuck::cast_slice(&batch.vertices);
+        if vertex_bytes.len() as u64 > slot.vertex.size() {
+            let count = (batch.vertices.len() as u64).next_power_of_two();
+            slot.vertex = Self::create_vertex_buffer(device, count);
+        }
+
+        let index_bytes: &[u8] = bytemuck::cast_slice(&batch.indices);
+        if index_bytes.len() as u64 > slot.index.size() {
+            let count = (batch.indices.len() as u64).next_power_of_two();
+            slot.index = Self::create_index_buffer(device, count);
+        }
+
+        queue.write_buffer(&slot.vertex, 0, vertex_bytes);
+        queue.write_buffer(&slot.index, 0, index_bytes);
+
+        slot
+    }
+
+    fn create_vertex_buffer(device: &wgpu::Device, count: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Vertex Buffer"),
+            size: count * std::mem::size_of::<Vertex>() as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn create_index_buffer(device: &wgpu::Device, count: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Index Buffer"),
+            size: count * std::mem::size_of::<u32>() as u64,
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+}
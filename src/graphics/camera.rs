@@ -0,0 +1,129 @@
+use glam::{Mat4, Vec2};
+
+/// An orthographic 2D camera.
+///
+/// At a zoom of 1.0 one world unit covers one physical pixel. World space is
+/// y-up with `position` at the centre of the viewport.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+    pub position: Vec2,
+    pub zoom: f32,
+    /// Rotation in radians, counter-clockwise.
+    pub rotation: f32,
+    /// Size of the viewport in physical pixels.
+    pub viewport: Vec2,
+}
+
+impl Camera2D {
+    /// Smallest zoom used for projection; lower values, including zero and
+    /// negative zooms, count as this.
+    pub const MIN_ZOOM: f32 = 1e-4;
+
+    pub fn new(viewport: Vec2) -> Self {
+        Self {
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport,
+        }
+    }
+
+    /// Viewport sides below one pixel, e.g. of a minimized window, count as
+    /// one pixel.
+    pub fn view_projection(&self) -> Mat4 {
+        let scale = 2.0 * self.effective_zoom() / self.viewport.max(Vec2::ONE);
+        Mat4::from_scale(scale.extend(1.0))
+            * Mat4::from_rotation_z(-self.rotation)
+            * Mat4::from_translation(-self.position.extend(0.0))
+    }
+
+    /// Converts a position in window pixels (origin top-left, y down) to world space.
+    pub fn screen_to_world(&self, screen: Vec2) -> Vec2 {
+        let offset = Vec2::new(
+            screen.x - self.viewport.x / 2.0,
+            self.viewport.y / 2.0 - screen.y,
+        ) / self.effective_zoom();
+        Vec2::from_angle(self.rotation).rotate(offset) + self.position
+    }
+
+    /// Converts a world-space position to window pixels (origin top-left, y down).
+    pub fn world_to_screen(&self, world: Vec2) -> Vec2 {
+        let offset =
+            Vec2::from_angle(-self.rotation).rotate(world - self.position) * self.effective_zoom();
+        Vec2::new(
+            offset.x + self.viewport.x / 2.0,
+            self.viewport.y / 2.0 - offset.y,
+        )
+    }
+
+    fn effective_zoom(&self) -> f32 {
+        self.zoom.max(Self::MIN_ZOOM)
+    }
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self::new(Vec2::ONE)
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct CameraUniform {
+    view_proj: [[f32; 4]; 4],
+}
+
+/// GPU side of a [`Camera2D`]: the uniform buffer and its bind group.
+pub struct CameraBinding {
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl CameraBinding {
+    pub fn new(device: &wgpu::Device, layout: &wgpu::BindGroupLayout) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Camera Buffer"),
+            size: std::mem::size_of::<CameraUniform>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        Self { buffer, bind_group }
+    }
+
+    pub fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Camera Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        })
+    }
+
+    pub fn update(&self, queue: &wgpu::Queue, camera: &Camera2D) {
+        let uniform = CameraUniform {
+            view_proj: camera.view_projection().to_cols_array_2d(),
+        };
+        queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(&uniform));
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+}
@@ -1,14 +1,21 @@
+use crate::core::EngineError;
 use std::sync::Arc;
 use wgpu::wgt::DeviceDescriptor;
 use wgpu::{
-    Backends, Device, Features, Limits, Queue, RequestAdapterOptions, Surface,
-    SurfaceConfiguration, TextureUsages,
+    Adapter, Backends, Device, Features, Instance, Limits, Queue, RequestAdapterOptions, Surface,
+    SurfaceConfiguration, Texture, TextureFormat, TextureUsages,
 };
 use winit::dpi::PhysicalSize;
 use winit::window::Window;
 
+/// Where frames are drawn to.
+pub enum RenderTarget {
+    Surface(Surface<'static>),
+    Texture(Texture),
+}
+
 pub struct GraphicsContext {
-    pub surface: Surface<'static>,
+    pub target: RenderTarget,
     pub device: Device,
     pub queue: Queue,
     pub config: SurfaceConfiguration,
@@ -16,78 +23,241 @@ pub struct GraphicsContext {
 }
 
 impl GraphicsContext {
-    pub async fn new(window: Arc<Window>) -> Self {
+    /// Fails with [`EngineError::UnsupportedSurface`] if the adapter reports
+    /// no formats, present modes or alpha modes for the window's surface.
+    pub async fn new(window: Arc<Window>) -> Result<Self, EngineError> {
         let size = window.inner_size();
 
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: Backends::all(),
-            ..Default::default()
-        });
+        let instance = Self::create_instance();
 
-        let surface = match instance.create_surface(window) {
-            Ok(surface) => surface,
-            Err(e) => panic!("Failed to create surface: {:?}", e),
-        };
+        let surface = instance.create_surface(window)?;
 
-        let adapter = match instance
+        let adapter = instance
             .request_adapter(&RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::default(),
                 compatible_surface: Some(&surface),
                 force_fallback_adapter: false,
             })
-            .await
-        {
-            Ok(adapter) => adapter,
-            Err(e) => panic!("Failed to create adapter: {:?}", e),
-        };
+            .await?;
 
-        let (device, queue) = match adapter
-            .request_device(&DeviceDescriptor {
-                label: Some("GPU Device"),
-                required_features: Features::empty(),
-                required_limits: Limits::default(),
-                ..Default::default()
-            })
-            .await
-        {
-            Ok(device) => device,
-            Err(e) => panic!("Failed to create device: {:?}", e),
-        };
+        let (device, queue) = Self::request_device(&adapter).await?;
 
         let surface_caps = surface.get_capabilities(&adapter);
+        let (Some(&first_format), Some(&present_mode), Some(&alpha_mode)) = (
+            surface_caps.formats.first(),
+            surface_caps.present_modes.first(),
+            surface_caps.alpha_modes.first(),
+        ) else {
+            return Err(EngineError::UnsupportedSurface(
+                "the adapter can't present to this window".into(),
+            ));
+        };
         let surface_format = surface_caps
             .formats
             .iter()
             .copied()
             .find(|f| f.is_srgb())
-            .unwrap_or(surface_caps.formats[0]);
+            .unwrap_or(first_format);
 
         let config = SurfaceConfiguration {
             usage: TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: surface_caps.present_modes[0],
-            alpha_mode: surface_caps.alpha_modes[0],
+            present_mode,
+            alpha_mode,
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
         };
 
         surface.configure(&device, &config);
 
-        Self {
-            surface,
+        Ok(Self {
+            target: RenderTarget::Surface(surface),
             device,
             queue,
             config,
             size,
+        })
+    }
+
+    /// Creates a context that renders into an offscreen RGBA8 texture.
+    ///
+    /// Falls back to a software adapter when no hardware adapter is available.
+    /// Fails with [`EngineError::Config`] if `size` is zero.
+    pub async fn new_headless(size: PhysicalSize<u32>) -> Result<Self, EngineError> {
+        if size.width == 0 || size.height == 0 {
+            return Err(EngineError::Config(format!(
+                "Render target size {}x{} is empty",
+                size.width, size.height
+            )));
         }
+
+        let instance = Self::create_instance();
+
+        let mut options = RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        };
+        let adapter = match instance.request_adapter(&options).await {
+            Ok(adapter) => adapter,
+            Err(_) => {
+                options.force_fallback_adapter = true;
+                instance.request_adapter(&options).await?
+            }
+        };
+
+        let (device, queue) = Self::request_device(&adapter).await?;
+
+        let config = SurfaceConfiguration {
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            format: TextureFormat::Rgba8UnormSrgb,
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        let texture = Self::create_target_texture(&device, &config);
+
+        Ok(Self {
+            target: RenderTarget::Texture(texture),
+            device,
+            queue,
+            config,
+            size,
+        })
+    }
+
+    fn create_instance() -> Instance {
+        Instance::new(&wgpu::InstanceDescriptor {
+            backends: Backends::all(),
+            ..Default::default()
+        })
+    }
+
+    async fn request_device(adapter: &Adapter) -> Result<(Device, Queue), EngineError> {
+        let (device, queue) = adapter
+            .request_device(&DeviceDescriptor {
+                label: Some("GPU Device"),
+                required_features: Features::empty(),
+                required_limits: Limits::default(),
+                ..Default::default()
+            })
+            .await?;
+        Ok((device, queue))
+    }
+
+    fn create_target_texture(device: &Device, config: &SurfaceConfiguration) -> Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen Target"),
+            size: wgpu::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: config.usage,
+            view_formats: &[],
+        })
     }
 
+    /// Reconfigures the target for `size`. Zero sizes, e.g. from a minimized
+    /// window, are ignored and the previous configuration is kept.
     pub fn resize(&mut self, size: PhysicalSize<u32>) {
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
         self.size = size;
         self.config.width = size.width;
         self.config.height = size.height;
-        self.surface.configure(&self.device, &self.config);
+        match &mut self.target {
+            RenderTarget::Surface(surface) => surface.configure(&self.device, &self.config),
+            RenderTarget::Texture(texture) => {
+                *texture = Self::create_target_texture(&self.device, &self.config)
+            }
+        }
+    }
+
+    pub fn set_present_mode(&mut self, present_mode: wgpu::PresentMode) {
+        self.config.present_mode = present_mode;
+        if let RenderTarget::Surface(surface) = &self.target {
+            surface.configure(&self.device, &self.config);
+        }
+    }
+
+    /// Copies the offscreen target back to the CPU as tightly packed RGBA8 rows.
+    ///
+    /// Returns `None` when rendering to a window surface.
+    pub fn read_pixels(&self) -> Option<Result<Vec<u8>, EngineError>> {
+        let RenderTarget::Texture(texture) = &self.target else {
+            return None;
+        };
+        Some(self.read_texture(texture))
+    }
+
+    fn read_texture(&self, texture: &Texture) -> Result<Vec<u8>, EngineError> {
+        let width = self.config.width;
+        let height = self.config.height;
+        let unpadded_bytes_per_row = width * 4;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback Buffer"),
+            size: (padded_bytes_per_row * height) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Readback Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+        self.device
+            .poll(wgpu::PollType::Wait)
+            .map_err(|e| EngineError::Readback(e.to_string()))?;
+        receiver
+            .recv()
+            .map_err(|e| EngineError::Readback(e.to_string()))?
+            .map_err(|e| EngineError::Readback(e.to_string()))?;
+
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        {
+            let data = buffer.slice(..).get_mapped_range();
+            for row in data.chunks(padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+            }
+        }
+        buffer.unmap();
+
+        Ok(pixels)
     }
 }
@@ -5,6 +5,7 @@ use crate::graphics::Color;
 pub struct Vertex {
     pub position: [f32; 3],
     pub color: [f32; 4],
+    pub uv: [f32; 2],
 }
 
 impl Vertex {
@@ -12,6 +13,35 @@ impl Vertex {
         Self {
             position,
             color: color.to_array(),
+            uv: [0.0, 0.0],
+        }
+    }
+
+    pub fn textured(position: [f32; 3], uv: [f32; 2], color: Color) -> Self {
+        Self {
+            position,
+            color: color.to_array(),
+            uv,
+        }
+    }
+}
+
+/// An axis-aligned rectangle given by its minimum corner and size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
         }
     }
 }
@@ -82,4 +112,22 @@ impl GeometryBuilder {
     pub fn quad(size: f32, color: Color) -> Geometry {
         Self::rectangle(size, size, color)
     }
+
+    /// A rectangle covering `dest` in world space, mapped to `uv` in texture
+    /// coordinates (0..1, origin at the top-left of the image).
+    pub fn textured_rect(dest: Rect, uv: Rect, color: Color) -> Geometry {
+        let (left, right) = (dest.x, dest.x + dest.width);
+        let (bottom, top) = (dest.y, dest.y + dest.height);
+        let (u0, u1) = (uv.x, uv.x + uv.width);
+        let (v0, v1) = (uv.y, uv.y + uv.height);
+
+        let vertices = vec![
+            Vertex::textured([left, bottom, 0.0], [u0, v1], color),
+            Vertex::textured([right, bottom, 0.0], [u1, v1], color),
+            Vertex::textured([right, top, 0.0], [u1, v0], color),
+            Vertex::textured([left, top, 0.0], [u0, v0], color),
+        ];
+        let indices = vec![0, 1, 2, 0, 2, 3];
+        Geometry::new(vertices, indices)
+    }
 }
\ No newline at end of file
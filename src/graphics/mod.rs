@@ -1,10 +1,18 @@
+mod batch;
+mod camera;
 mod color;
 mod context;
 mod geometry;
 mod pipeline;
 mod renderer;
+mod texture;
+mod transform;
 
+pub use batch::FrameStats;
+pub use camera::Camera2D;
 pub use color::Color;
-use context::GraphicsContext;
-pub use geometry::{Geometry, GeometryBuilder, Vertex};
-pub use renderer::Renderer;
+use context::{GraphicsContext, RenderTarget};
+pub use geometry::{Geometry, GeometryBuilder, Rect, Vertex};
+pub use renderer::{Frame, Renderer};
+pub use texture::Texture;
+pub use transform::{InterpolatedTransform, Transform2D};
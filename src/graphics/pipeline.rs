@@ -1,12 +1,43 @@
-use wgpu::util::DeviceExt;
-use super::geometry::{Geometry, Vertex};
+use super::geometry::Vertex;
 
 pub struct RenderPipeline {
     pipeline: wgpu::RenderPipeline,
 }
 
 impl RenderPipeline {
-    pub fn new(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> Self {
+    /// Pipeline for untextured shapes, coloured by their vertices.
+    pub fn new(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        camera_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        Self::create(device, config, &[camera_layout], "fs_main", "Render Pipeline")
+    }
+
+    /// Pipeline for sprites: samples the texture in bind group 1 and tints it
+    /// with the vertex colour.
+    pub fn textured(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        camera_layout: &wgpu::BindGroupLayout,
+        texture_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        Self::create(
+            device,
+            config,
+            &[camera_layout, texture_layout],
+            "fs_textured",
+            "Textured Render Pipeline",
+        )
+    }
+
+    fn create(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+        fragment_entry_point: &str,
+        label: &str,
+    ) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
@@ -14,17 +45,18 @@ impl RenderPipeline {
 
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Pipeline Layout"),
-            bind_group_layouts: &[], //TODO
+            bind_group_layouts,
             push_constant_ranges: &[],
         });
 
         let vertex_attributes = wgpu::vertex_attr_array![
                 0 => Float32x3,
-                1 => Float32x4
+                1 => Float32x4,
+                2 => Float32x2
         ];
 
         let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
+            label: Some(label),
             layout: Some(&pipeline_layout),
 
             vertex: wgpu::VertexState {
@@ -40,7 +72,7 @@ impl RenderPipeline {
 
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
-                entry_point: Some("fs_main"),
+                entry_point: Some(fragment_entry_point),
                 compilation_options: Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: config.format,
@@ -71,20 +103,4 @@ impl RenderPipeline {
     pub fn get_pipeline(&self) -> &wgpu::RenderPipeline {
         &self.pipeline
     }
-
-    pub fn create_buffers(&self, device: &wgpu::Device, geometry: &Geometry) -> (wgpu::Buffer, wgpu::Buffer) {
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Vertex Buffer"),
-            contents: bytemuck::cast_slice(&geometry.vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Index Buffer"),
-            contents: bytemuck::cast_slice(&geometry.indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-
-        (vertex_buffer, index_buffer)
-    }
 }
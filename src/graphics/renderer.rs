@@ -1,33 +1,98 @@
-use super::{Color, GraphicsContext, Geometry, GeometryBuilder};
+use super::batch::{Batch, BufferRing, FrameStats};
+use super::camera::{Camera2D, CameraBinding};
 use super::pipeline::RenderPipeline;
+use super::texture::{Texture, TextureLayout};
+use super::transform::Transform2D;
+use super::{Color, Geometry, GeometryBuilder, GraphicsContext, Rect, RenderTarget};
+use crate::core::EngineError;
+use glam::Vec2;
 use std::iter;
+use std::path::Path;
 use std::sync::Arc;
-use wgpu::StoreOp::Store;
 use wgpu::{
     CommandEncoder, LoadOp, RenderPassColorAttachment, RenderPassDescriptor, StoreOp,
     SurfaceTexture, TextureView,
 };
-use winit::dpi::{PhysicalSize, PhysicalUnit};
+use winit::dpi::PhysicalSize;
 use winit::window::Window;
 
 pub struct Renderer {
     context: GraphicsContext,
     pipeline: RenderPipeline,
+    textured_pipeline: RenderPipeline,
+    texture_layout: TextureLayout,
+    buffers: BufferRing,
+    batch: Batch,
+    stats: FrameStats,
+    camera: Camera2D,
+    camera_binding: CameraBinding,
 }
 
 impl Renderer {
-    pub async fn new(window: Arc<Window>) -> Self {
-        let context = GraphicsContext::new(window).await;
-        let pipeline = RenderPipeline::new(&context.device, &context.config);
-        Self { context, pipeline }
+    pub async fn new(window: Arc<Window>) -> Result<Self, EngineError> {
+        let context = GraphicsContext::new(window).await?;
+        Self::from_context(context).await
     }
 
-    pub fn begin_frame(&mut self) -> Option<Frame<'_>> {
-        let surface_texture = self.context.surface.get_current_texture().ok()?;
+    /// Creates a renderer without a window that draws into an offscreen texture.
+    ///
+    /// Use [`Renderer::read_pixels`] after presenting a frame to get the result.
+    pub async fn new_headless(width: u32, height: u32) -> Result<Self, EngineError> {
+        let context = GraphicsContext::new_headless(PhysicalSize::new(width, height)).await?;
+        Self::from_context(context).await
+    }
+
+    async fn from_context(context: GraphicsContext) -> Result<Self, EngineError> {
+        context
+            .device
+            .push_error_scope(wgpu::ErrorFilter::Validation);
+        let camera_layout = CameraBinding::bind_group_layout(&context.device);
+        let texture_layout = TextureLayout::new(&context.device);
+        let pipeline = RenderPipeline::new(&context.device, &context.config, &camera_layout);
+        let textured_pipeline = RenderPipeline::textured(
+            &context.device,
+            &context.config,
+            &camera_layout,
+            &texture_layout.layout,
+        );
+        if let Some(e) = context.device.pop_error_scope().await {
+            return Err(EngineError::Shader(e.to_string()));
+        }
 
-        let view = surface_texture
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let buffers = BufferRing::new(&context.device);
+        let camera = Camera2D::new(Vec2::new(
+            context.size.width as f32,
+            context.size.height as f32,
+        ));
+        let camera_binding = CameraBinding::new(&context.device, &camera_layout);
+
+        Ok(Self {
+            context,
+            pipeline,
+            textured_pipeline,
+            texture_layout,
+            buffers,
+            batch: Batch::default(),
+            stats: FrameStats::default(),
+            camera,
+            camera_binding,
+        })
+    }
+
+    pub fn begin_frame(&mut self) -> Option<Frame<'_>> {
+        let (surface_texture, view) = match &self.context.target {
+            RenderTarget::Surface(surface) => {
+                let surface_texture = surface.get_current_texture().ok()?;
+                let view = surface_texture
+                    .texture
+                    .create_view(&wgpu::TextureViewDescriptor::default());
+                (Some(surface_texture), view)
+            }
+            RenderTarget::Texture(texture) => {
+                let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+                (None, view)
+            }
+        };
 
         let encoder = self
             .context
@@ -36,75 +101,178 @@ impl Renderer {
                 label: Some("Render Encoder"),
             });
 
+        self.batch.clear();
+
         Some(Frame {
             surface_texture,
             view,
             encoder,
             context: &self.context,
             pipeline: &self.pipeline,
+            textured_pipeline: &self.textured_pipeline,
+            buffers: &mut self.buffers,
+            batch: &mut self.batch,
+            stats: &mut self.stats,
+            camera: &mut self.camera,
+            camera_binding: &self.camera_binding,
+            clear_color: None,
+            draw_calls: 0,
         })
     }
 
+    /// Resizes the target and the camera viewport. Zero sizes, e.g. from a
+    /// minimized window, keep the previous size.
     pub fn resize(&mut self, new_size: PhysicalSize<u32>) {
+        if new_size.width == 0 || new_size.height == 0 {
+            return;
+        }
         self.context.resize(new_size);
+        self.camera.viewport = Vec2::new(new_size.width as f32, new_size.height as f32);
+    }
+
+    pub fn camera(&self) -> &Camera2D {
+        &self.camera
+    }
+
+    /// Waits for the display's refresh when presenting, or presents as soon
+    /// as possible if `vsync` is false.
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.context.set_present_mode(if vsync {
+            wgpu::PresentMode::AutoVsync
+        } else {
+            wgpu::PresentMode::AutoNoVsync
+        });
+    }
+
+    /// Uploads tightly packed sRGB RGBA8 pixels as a texture. See
+    /// [`Texture::from_rgba8`] for when this fails.
+    pub fn create_texture(
+        &self,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+    ) -> Result<Texture, EngineError> {
+        Texture::from_rgba8(
+            &self.context.device,
+            &self.context.queue,
+            &self.texture_layout,
+            width,
+            height,
+            pixels,
+        )
+    }
+
+    /// Decodes PNG or JPEG bytes into a texture.
+    pub fn load_texture_from_bytes(&self, bytes: &[u8]) -> Result<Texture, EngineError> {
+        Texture::from_bytes(
+            &self.context.device,
+            &self.context.queue,
+            &self.texture_layout,
+            bytes,
+        )
+    }
+
+    /// Reads and decodes a PNG or JPEG file into a texture.
+    pub fn load_texture(&self, path: impl AsRef<Path>) -> Result<Texture, EngineError> {
+        Texture::from_file(
+            &self.context.device,
+            &self.context.queue,
+            &self.texture_layout,
+            path,
+        )
+    }
+
+    /// The camera used for every frame. Its viewport follows the window size.
+    pub fn camera_mut(&mut self) -> &mut Camera2D {
+        &mut self.camera
+    }
+
+    /// Statistics for the most recently presented frame.
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.context.size
+    }
+
+    /// Reads the last presented frame back as RGBA8 pixels, row by row.
+    ///
+    /// Returns `None` for window-backed renderers.
+    pub fn read_pixels(&self) -> Option<Result<Vec<u8>, EngineError>> {
+        self.context.read_pixels()
     }
 }
 
 pub struct Frame<'a> {
-    surface_texture: SurfaceTexture,
+    surface_texture: Option<SurfaceTexture>,
     view: TextureView,
     encoder: CommandEncoder,
     context: &'a GraphicsContext,
     pipeline: &'a RenderPipeline,
+    textured_pipeline: &'a RenderPipeline,
+    buffers: &'a mut BufferRing,
+    batch: &'a mut Batch,
+    stats: &'a mut FrameStats,
+    camera: &'a mut Camera2D,
+    camera_binding: &'a CameraBinding,
+    clear_color: Option<Color>,
+    draw_calls: u32,
 }
 
-impl<'a> Frame<'_> {
+impl Frame<'_> {
+    pub fn camera(&self) -> &Camera2D {
+        self.camera
+    }
+
+    /// The camera applied to everything drawn this frame.
+    pub fn camera_mut(&mut self) -> &mut Camera2D {
+        self.camera
+    }
+
+    /// Clears the frame to `color`, discarding anything drawn before.
     pub fn clear(&mut self, color: Color) {
-        self.encoder.begin_render_pass(&RenderPassDescriptor {
-            label: Some("Clear Pass"),
-            color_attachments: &[Some(RenderPassColorAttachment {
-                view: &self.view,
-                depth_slice: None,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: LoadOp::Clear(wgpu::Color {
-                        r: color.r as f64,
-                        g: color.g as f64,
-                        b: color.b as f64,
-                        a: color.a as f64,
-                    }),
-                    store: StoreOp::Store,
-                },
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
+        self.batch.clear();
+        self.draw_calls = 0;
+        self.clear_color = Some(color);
     }
 
     pub fn draw_geometry(&mut self, geometry: &Geometry) {
-        let (vertex_buffer, index_buffer) = self.pipeline.create_buffers(&self.context.device, geometry);
-
-        let mut render_pass = self.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Shape Render Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &self.view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
+        self.batch.push(&geometry.vertices, &geometry.indices, None);
+        self.draw_calls += 1;
+    }
 
-        render_pass.set_pipeline(self.pipeline.get_pipeline());
-        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-        render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-        render_pass.draw_indexed(0..geometry.indices.len() as u32, 0, 0..1);
+    pub fn draw_geometry_with(&mut self, geometry: &Geometry, transform: &Transform2D) {
+        self.batch
+            .push_transformed(&geometry.vertices, &geometry.indices, transform, None);
+        self.draw_calls += 1;
+    }
+
+    /// Draws the `src_rect` region of `texture` (in texture pixels, origin
+    /// top-left) into `dest_rect` in world space, multiplied by `tint`.
+    /// A `src_rect` of `None` uses the whole texture.
+    pub fn draw_sprite(
+        &mut self,
+        texture: &Texture,
+        dest_rect: Rect,
+        src_rect: Option<Rect>,
+        tint: Color,
+    ) {
+        let (width, height) = (texture.width() as f32, texture.height() as f32);
+        let uv = match src_rect {
+            Some(src) => Rect::new(
+                src.x / width,
+                src.y / height,
+                src.width / width,
+                src.height / height,
+            ),
+            None => Rect::new(0.0, 0.0, 1.0, 1.0),
+        };
+
+        let geometry = GeometryBuilder::textured_rect(dest_rect, uv, tint);
+        self.batch
+            .push(&geometry.vertices, &geometry.indices, Some(texture));
+        self.draw_calls += 1;
     }
 
     pub fn draw_triangle(&mut self, size: f32, color: Color) {
@@ -127,8 +295,100 @@ impl<'a> Frame<'_> {
         self.draw_geometry(&geometry);
     }
 
-    pub fn present(self) {
+    pub fn draw_triangle_at(&mut self, size: f32, color: Color, transform: &Transform2D) {
+        let geometry = GeometryBuilder::triangle(size, color);
+        self.draw_geometry_with(&geometry, transform);
+    }
+
+    pub fn draw_rectangle_at(
+        &mut self,
+        width: f32,
+        height: f32,
+        color: Color,
+        transform: &Transform2D,
+    ) {
+        let geometry = GeometryBuilder::rectangle(width, height, color);
+        self.draw_geometry_with(&geometry, transform);
+    }
+
+    pub fn draw_circle_at(
+        &mut self,
+        radius: f32,
+        segments: u32,
+        color: Color,
+        transform: &Transform2D,
+    ) {
+        let geometry = GeometryBuilder::circle(radius, segments, color);
+        self.draw_geometry_with(&geometry, transform);
+    }
+
+    pub fn draw_quad_at(&mut self, size: f32, color: Color, transform: &Transform2D) {
+        let geometry = GeometryBuilder::quad(size, color);
+        self.draw_geometry_with(&geometry, transform);
+    }
+
+    /// Uploads the recorded geometry, draws it in a single pass and presents.
+    pub fn present(mut self) {
+        let load = match self.clear_color {
+            Some(color) => LoadOp::Clear(wgpu::Color {
+                r: color.r as f64,
+                g: color.g as f64,
+                b: color.b as f64,
+                a: color.a as f64,
+            }),
+            None => LoadOp::Load,
+        };
+
+        self.camera_binding.update(&self.context.queue, self.camera);
+
+        let slot = self
+            .buffers
+            .upload(&self.context.device, &self.context.queue, self.batch);
+
+        {
+            let mut render_pass = self.encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Frame Render Pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &self.view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load,
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            if !self.batch.is_empty() {
+                render_pass.set_bind_group(0, self.camera_binding.bind_group(), &[]);
+                render_pass.set_vertex_buffer(0, slot.vertex.slice(..));
+                render_pass.set_index_buffer(slot.index.slice(..), wgpu::IndexFormat::Uint32);
+                for range in &self.batch.ranges {
+                    match &range.texture {
+                        Some(texture) => {
+                            render_pass.set_pipeline(self.textured_pipeline.get_pipeline());
+                            render_pass.set_bind_group(1, texture.bind_group(), &[]);
+                        }
+                        None => render_pass.set_pipeline(self.pipeline.get_pipeline()),
+                    }
+                    render_pass.draw_indexed(range.indices.clone(), 0, 0..1);
+                }
+            }
+        }
+
+        *self.stats = FrameStats {
+            draw_calls: self.draw_calls,
+            batches: self.batch.ranges.len() as u32,
+            vertices: self.batch.vertices.len() as u32,
+            indices: self.batch.indices.len() as u32,
+        };
+
         self.context.queue.submit(iter::once(self.encoder.finish()));
-        self.surface_texture.present();
+        if let Some(surface_texture) = self.surface_texture {
+            surface_texture.present();
+        }
     }
 }
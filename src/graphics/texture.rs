@@ -0,0 +1,188 @@
+use crate::core::EngineError;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_TEXTURE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An RGBA image uploaded to the GPU, ready to be drawn with
+/// [`Frame::draw_sprite`](super::Frame::draw_sprite).
+///
+/// Cloning is cheap and shares the same GPU texture.
+#[derive(Debug, Clone)]
+pub struct Texture {
+    id: u64,
+    width: u32,
+    height: u32,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Texture {
+    /// Uploads tightly packed sRGB RGBA8 pixels. Fails if the size is zero,
+    /// larger than the device supports, or doesn't match `pixels`.
+    pub fn from_rgba8(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &TextureLayout,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+    ) -> Result<Self, EngineError> {
+        if width == 0 || height == 0 {
+            return Err(EngineError::Asset(format!(
+                "Texture size {}x{} is empty",
+                width, height
+            )));
+        }
+        let max = device.limits().max_texture_dimension_2d;
+        if width > max || height > max {
+            return Err(EngineError::Asset(format!(
+                "Texture size {}x{} exceeds the device limit of {}",
+                width, height, max
+            )));
+        }
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            return Err(EngineError::Asset(format!(
+                "Expected {} bytes of RGBA8 pixels for a {}x{} texture, got {}",
+                expected,
+                width,
+                height,
+                pixels.len()
+            )));
+        }
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Sprite Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            texture.as_image_copy(),
+            pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Sprite Bind Group"),
+            layout: &layout.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&layout.sampler),
+                },
+            ],
+        });
+
+        Ok(Self {
+            id: NEXT_TEXTURE_ID.fetch_add(1, Ordering::Relaxed),
+            width,
+            height,
+            bind_group,
+        })
+    }
+
+    /// Decodes a PNG or JPEG image and uploads it.
+    pub fn from_bytes(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &TextureLayout,
+        bytes: &[u8],
+    ) -> Result<Self, EngineError> {
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| EngineError::Asset(e.to_string()))?
+            .into_rgba8();
+        Self::from_rgba8(device, queue, layout, image.width(), image.height(), &image)
+    }
+
+    pub fn from_file(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &TextureLayout,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, EngineError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(device, queue, layout, &bytes)
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+}
+
+/// The bind group layout and sampler shared by every sprite texture.
+pub struct TextureLayout {
+    pub layout: wgpu::BindGroupLayout,
+    pub sampler: wgpu::Sampler,
+}
+
+impl TextureLayout {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Texture Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        // Nearest filtering keeps pixel art crisp when sprites are scaled up.
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Sprite Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self { layout, sampler }
+    }
+}
@@ -0,0 +1,111 @@
+use glam::{Affine2, Vec2};
+use std::f32::consts::{PI, TAU};
+
+/// Placement of a shape in world space.
+///
+/// `anchor` is the point in the geometry's local space that lands on
+/// `position` and that rotation and scale pivot around. Shapes from
+/// [`GeometryBuilder`](super::GeometryBuilder) are centred on the origin, so
+/// the default anchor is their centre.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    pub position: Vec2,
+    /// Rotation in radians, counter-clockwise.
+    pub rotation: f32,
+    pub scale: Vec2,
+    pub anchor: Vec2,
+}
+
+impl Transform2D {
+    pub const IDENTITY: Transform2D = Transform2D {
+        position: Vec2::ZERO,
+        rotation: 0.0,
+        scale: Vec2::ONE,
+        anchor: Vec2::ZERO,
+    };
+
+    pub fn from_position(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: Vec2) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn matrix(&self) -> Affine2 {
+        Affine2::from_scale_angle_translation(self.scale, self.rotation, self.position)
+            * Affine2::from_translation(-self.anchor)
+    }
+
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.matrix().transform_point2(point)
+    }
+
+    /// Blends towards `other` by `t` in 0..1, rotating the short way round.
+    pub fn lerp(&self, other: &Transform2D, t: f32) -> Transform2D {
+        let turn = (other.rotation - self.rotation + PI).rem_euclid(TAU) - PI;
+        Transform2D {
+            position: self.position.lerp(other.position, t),
+            rotation: self.rotation + turn * t,
+            scale: self.scale.lerp(other.scale, t),
+            anchor: self.anchor.lerp(other.anchor, t),
+        }
+    }
+}
+
+/// The transform of an object moved in `fixed_update`, smoothed for drawing
+/// between ticks.
+///
+/// Call [`InterpolatedTransform::snapshot`] at the start of every tick before
+/// moving `current`, then draw with
+/// [`InterpolatedTransform::interpolate`] using the `alpha` passed to
+/// `Game::render`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct InterpolatedTransform {
+    pub previous: Transform2D,
+    pub current: Transform2D,
+}
+
+impl InterpolatedTransform {
+    pub fn new(transform: Transform2D) -> Self {
+        Self {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Remembers the current transform as the start of the next tick.
+    pub fn snapshot(&mut self) {
+        self.previous = self.current;
+    }
+
+    /// Moves to `transform` without interpolating, e.g. when respawning.
+    pub fn teleport(&mut self, transform: Transform2D) {
+        self.previous = transform;
+        self.current = transform;
+    }
+
+    pub fn interpolate(&self, alpha: f32) -> Transform2D {
+        self.previous.lerp(&self.current, alpha)
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
@@ -0,0 +1,114 @@
+use super::axis::{Axis, Axis2D};
+use super::gamepad::GamepadButton;
+use crate::core::EngineError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// A physical input that can trigger an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// A button on any connected gamepad.
+    Gamepad(GamepadButton),
+}
+
+/// Named actions ("jump", "move_left") mapped to one or more bindings, plus
+/// named analog axes.
+///
+/// Serialises to TOML as tables of names to bindings:
+///
+/// ```toml
+/// [actions]
+/// jump = [{ key = "Space" }, { mouse = "Left" }, { gamepad = "South" }]
+///
+/// [axes.steer]
+/// sources = [{ keys = { negative = "KeyA", positive = "KeyD" } }]
+///
+/// [axes_2d.look]
+/// x = ["mouse_x"]
+/// y = ["mouse_y"]
+/// settings = { sensitivity = 0.1 }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionMap {
+    #[serde(default)]
+    actions: BTreeMap<String, Vec<Binding>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    axes: BTreeMap<String, Axis>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    axes_2d: BTreeMap<String, Axis2D>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `binding` to `action`, creating the action if needed.
+    pub fn bind(&mut self, action: impl Into<String>, binding: Binding) {
+        let bindings = self.actions.entry(action.into()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn unbind(&mut self, action: &str, binding: Binding) {
+        if let Some(bindings) = self.actions.get_mut(action) {
+            bindings.retain(|b| *b != binding);
+        }
+    }
+
+    /// Replaces every binding of `action`, e.g. after the player rebinds it.
+    pub fn set_bindings(&mut self, action: impl Into<String>, bindings: Vec<Binding>) {
+        self.actions.insert(action.into(), bindings);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+
+    /// Sets the axis queried by [`Input::axis`](super::Input::axis).
+    pub fn bind_axis(&mut self, name: impl Into<String>, axis: Axis) {
+        self.axes.insert(name.into(), axis);
+    }
+
+    pub fn axis(&self, name: &str) -> Option<&Axis> {
+        self.axes.get(name)
+    }
+
+    /// Sets the axis queried by [`Input::axis_2d`](super::Input::axis_2d).
+    pub fn bind_axis_2d(&mut self, name: impl Into<String>, axis: Axis2D) {
+        self.axes_2d.insert(name.into(), axis);
+    }
+
+    pub fn axis_2d(&self, name: &str) -> Option<&Axis2D> {
+        self.axes_2d.get(name)
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, EngineError> {
+        toml::from_str(source).map_err(|e| EngineError::Config(e.to_string()))
+    }
+
+    pub fn to_toml(&self) -> Result<String, EngineError> {
+        toml::to_string(self).map_err(|e| EngineError::Config(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        let source = std::fs::read_to_string(path)?;
+        Self::from_toml(&source)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EngineError> {
+        std::fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use super::gamepad::GamepadAxis;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use winit::keyboard::KeyCode;
+
+/// Something that produces an analog value for an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxisSource {
+    /// -1 while `negative` is held, +1 while `positive` is held, 0 for both or neither.
+    Keys {
+        negative: KeyCode,
+        positive: KeyCode,
+    },
+    /// Horizontal mouse motion this frame, in raw device units.
+    MouseX,
+    /// Vertical mouse motion this frame, in raw device units, positive downwards.
+    MouseY,
+    /// A gamepad stick or trigger. With several gamepads the value furthest
+    /// from rest wins.
+    Gamepad(GamepadAxis),
+}
+
+impl AxisSource {
+    /// Whether the source stays within -1..1. Unbounded sources such as mouse
+    /// motion are never clamped.
+    pub fn is_bounded(&self) -> bool {
+        matches!(self, AxisSource::Keys { .. } | AxisSource::Gamepad(_))
+    }
+}
+
+/// Maps the magnitude of an axis after the dead zone has been removed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseCurve {
+    Linear,
+    /// Raises the magnitude to this power; values above 1 give finer control
+    /// near the centre.
+    Power(f32),
+}
+
+impl ResponseCurve {
+    fn apply(&self, magnitude: f32) -> f32 {
+        match self {
+            ResponseCurve::Linear => magnitude,
+            ResponseCurve::Power(exponent) => magnitude.powf(*exponent),
+        }
+    }
+}
+
+/// Shaping applied to the bounded sources of an axis. Unbounded sources such
+/// as mouse motion are only scaled by the sensitivity.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisSettings {
+    /// Magnitudes below this are reported as zero; the rest is rescaled so
+    /// the output still starts at zero. Kept within 0..1.
+    pub dead_zone: f32,
+    /// Multiplier applied to the raw value before the dead zone.
+    pub sensitivity: f32,
+    pub curve: ResponseCurve,
+}
+
+impl Default for AxisSettings {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.0,
+            sensitivity: 1.0,
+            curve: ResponseCurve::Linear,
+        }
+    }
+}
+
+impl AxisSettings {
+    /// Largest usable dead zone; a dead zone of 1 would leave no range.
+    const MAX_DEAD_ZONE: f32 = 0.99;
+
+    fn shape(&self, magnitude: f32) -> f32 {
+        let dead_zone = self.dead_zone.clamp(0.0, Self::MAX_DEAD_ZONE);
+        let magnitude = magnitude * self.sensitivity;
+        if magnitude <= dead_zone {
+            return 0.0;
+        }
+        self.curve
+            .apply((magnitude - dead_zone) / (1.0 - dead_zone))
+            .min(1.0)
+    }
+
+    /// Applies sensitivity, dead zone and curve to a 1D value, keeping its
+    /// sign. The result is within -1..1.
+    pub fn apply(&self, value: f32) -> f32 {
+        self.shape(value.abs()).copysign(value)
+    }
+
+    /// Applies sensitivity, a radial dead zone and the curve to a 2D value,
+    /// keeping its direction. The result is at most unit length.
+    pub fn apply_2d(&self, value: Vec2) -> Vec2 {
+        let length = value.length();
+        if length == 0.0 {
+            return Vec2::ZERO;
+        }
+        value / length * self.shape(length)
+    }
+}
+
+/// A 1D axis, e.g. A/D mapped to -1..1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Axis {
+    pub sources: Vec<AxisSource>,
+    #[serde(default)]
+    pub settings: AxisSettings,
+}
+
+impl Axis {
+    pub fn new(sources: Vec<AxisSource>) -> Self {
+        Self {
+            sources,
+            settings: AxisSettings::default(),
+        }
+    }
+
+    pub fn keys(negative: KeyCode, positive: KeyCode) -> Self {
+        Self::new(vec![AxisSource::Keys { negative, positive }])
+    }
+
+    pub fn with_settings(mut self, settings: AxisSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+}
+
+/// A 2D axis composed of one set of sources per component, e.g. WASD.
+///
+/// Bounded sources are combined and clamped to unit length so diagonals are
+/// not faster than straight movement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Axis2D {
+    pub x: Vec<AxisSource>,
+    pub y: Vec<AxisSource>,
+    #[serde(default)]
+    pub settings: AxisSettings,
+}
+
+impl Axis2D {
+    pub fn new(x: Vec<AxisSource>, y: Vec<AxisSource>) -> Self {
+        Self {
+            x,
+            y,
+            settings: AxisSettings::default(),
+        }
+    }
+
+    /// Four keys as a composite axis with y pointing up.
+    pub fn keys(left: KeyCode, right: KeyCode, down: KeyCode, up: KeyCode) -> Self {
+        Self::new(
+            vec![AxisSource::Keys {
+                negative: left,
+                positive: right,
+            }],
+            vec![AxisSource::Keys {
+                negative: down,
+                positive: up,
+            }],
+        )
+    }
+
+    pub fn wasd() -> Self {
+        Self::keys(KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyS, KeyCode::KeyW)
+    }
+
+    pub fn arrows() -> Self {
+        Self::keys(
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowRight,
+            KeyCode::ArrowDown,
+            KeyCode::ArrowUp,
+        )
+    }
+
+    /// Raw mouse motion as a 2D axis.
+    pub fn mouse() -> Self {
+        Self::new(vec![AxisSource::MouseX], vec![AxisSource::MouseY])
+    }
+
+    /// The left stick of any gamepad.
+    pub fn left_stick() -> Self {
+        Self::new(
+            vec![AxisSource::Gamepad(GamepadAxis::LeftStickX)],
+            vec![AxisSource::Gamepad(GamepadAxis::LeftStickY)],
+        )
+    }
+
+    /// The right stick of any gamepad.
+    pub fn right_stick() -> Self {
+        Self::new(
+            vec![AxisSource::Gamepad(GamepadAxis::RightStickX)],
+            vec![AxisSource::Gamepad(GamepadAxis::RightStickY)],
+        )
+    }
+
+    pub fn with_settings(mut self, settings: AxisSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Which consumer is currently reading input.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputPhase {
+    /// Per-frame code: `Game::update` and `Game::render`.
+    #[default]
+    Frame,
+    /// A fixed simulation step: `Game::fixed_update`.
+    Tick,
+}
+
+#[derive(Debug)]
+struct Edges<T> {
+    pressed: HashSet<T>,
+    released: HashSet<T>,
+}
+
+impl<T> Default for Edges<T> {
+    fn default() -> Self {
+        Self {
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+        }
+    }
+}
+
+impl<T> Edges<T> {
+    fn clear(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+}
+
+/// Held state plus separate press/release edges for frames and fixed ticks.
+///
+/// An edge is kept in both buffers until the matching consumer has run, so a
+/// press is seen by exactly one tick and exactly one frame no matter how many
+/// ticks run per frame.
+#[derive(Debug)]
+pub struct ButtonState<T> {
+    pressed: HashSet<T>,
+    /// Held buttons hidden from queries until they are released.
+    consumed: HashSet<T>,
+    frame: Edges<T>,
+    tick: Edges<T>,
+    phase: InputPhase,
+}
+
+impl<T> Default for ButtonState<T> {
+    fn default() -> Self {
+        Self {
+            pressed: HashSet::new(),
+            consumed: HashSet::new(),
+            frame: Edges::default(),
+            tick: Edges::default(),
+            phase: InputPhase::default(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> ButtonState<T> {
+    /// Records a press. Repeats of a button that is already held are ignored.
+    pub fn press(&mut self, button: T) {
+        if self.pressed.insert(button.clone()) {
+            self.frame.pressed.insert(button.clone());
+            self.tick.pressed.insert(button);
+        }
+    }
+
+    /// Records a release. The release of a consumed button is not reported.
+    pub fn release(&mut self, button: T) {
+        if self.pressed.remove(&button) && !self.consumed.remove(&button) {
+            self.frame.released.insert(button.clone());
+            self.tick.released.insert(button);
+        }
+    }
+
+    /// Releases every held button, e.g. when the window loses focus and the
+    /// real releases will never arrive.
+    pub fn release_all(&mut self) {
+        let held: Vec<T> = self.pressed.iter().cloned().collect();
+        for button in held {
+            self.release(button);
+        }
+    }
+
+    /// Hides `button` from every query until it is released, and drops any
+    /// pending edges for it.
+    pub fn consume(&mut self, button: &T) {
+        if self.pressed.contains(button) {
+            self.consumed.insert(button.clone());
+        }
+        for edges in [&mut self.frame, &mut self.tick] {
+            edges.pressed.remove(button);
+            edges.released.remove(button);
+        }
+    }
+
+    /// Consumes every held button and drops all pending edges.
+    pub fn consume_all(&mut self) {
+        self.consumed.extend(self.pressed.iter().cloned());
+        self.frame.clear();
+        self.tick.clear();
+    }
+
+    pub fn set_phase(&mut self, phase: InputPhase) {
+        self.phase = phase;
+    }
+
+    pub fn end_frame(&mut self) {
+        self.frame.clear();
+    }
+
+    pub fn end_tick(&mut self) {
+        self.tick.clear();
+    }
+
+    pub fn is_pressed(&self, button: &T) -> bool {
+        self.pressed.contains(button) && !self.consumed.contains(button)
+    }
+
+    pub fn is_just_pressed(&self, button: &T) -> bool {
+        self.edges().pressed.contains(button)
+    }
+
+    pub fn is_just_released(&self, button: &T) -> bool {
+        self.edges().released.contains(button)
+    }
+
+    pub fn just_pressed(&self) -> impl Iterator<Item = &T> {
+        self.edges().pressed.iter()
+    }
+
+    fn edges(&self) -> &Edges<T> {
+        match self.phase {
+            InputPhase::Frame => &self.frame,
+            InputPhase::Tick => &self.tick,
+        }
+    }
+}
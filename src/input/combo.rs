@@ -0,0 +1,251 @@
+use super::button::InputPhase;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// One step of a [`Combo`]: a set of actions held together, entered by
+/// pressing at least one of them, or held for a minimum number of ticks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComboStep {
+    pub actions: Vec<String>,
+    /// Ticks the actions must have been held for. 0 means the step needs a
+    /// fresh press instead.
+    pub charge: u32,
+}
+
+impl ComboStep {
+    pub fn press(action: impl Into<String>) -> Self {
+        Self::chord([action])
+    }
+
+    /// Several actions held at once, e.g. down and forward for a diagonal.
+    pub fn chord<S: Into<String>>(actions: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            actions: actions.into_iter().map(Into::into).collect(),
+            charge: 0,
+        }
+    }
+
+    /// `action` held for at least `ticks` ticks.
+    pub fn charge(action: impl Into<String>, ticks: u32) -> Self {
+        Self {
+            actions: vec![action.into()],
+            charge: ticks,
+        }
+    }
+
+    fn matches(&self, snapshot: &Snapshot) -> bool {
+        let held = |action: &String| snapshot.held.get(action).copied().unwrap_or(0);
+        if self.charge > 0 {
+            self.actions
+                .iter()
+                .all(|action| held(action) >= self.charge)
+        } else {
+            self.actions.iter().all(|action| held(action) > 0)
+                && self
+                    .actions
+                    .iter()
+                    .any(|action| snapshot.pressed.contains(action))
+        }
+    }
+}
+
+/// A sequence of steps that must be entered in order, e.g. a quarter-circle
+/// forward plus punch:
+///
+/// ```
+/// # use engine::input::{Combo, ComboStep};
+/// let hadoken = Combo::new(vec![
+///     ComboStep::press("down"),
+///     ComboStep::chord(["down", "forward"]),
+///     ComboStep::chord(["forward", "punch"]),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combo {
+    pub steps: Vec<ComboStep>,
+    /// Maximum ticks between the first and the last step.
+    pub window: u64,
+    /// Maximum ticks between two consecutive steps.
+    pub leniency: u64,
+}
+
+impl Combo {
+    pub fn new(steps: Vec<ComboStep>) -> Self {
+        Self {
+            steps,
+            window: 30,
+            leniency: 10,
+        }
+    }
+
+    pub fn with_window(mut self, ticks: u64) -> Self {
+        self.window = ticks;
+        self
+    }
+
+    pub fn with_leniency(mut self, ticks: u64) -> Self {
+        self.leniency = ticks;
+        self
+    }
+
+    /// The tick the sequence started on if it was completed at the newest
+    /// snapshot in `history`, after `after` if given. The latest start wins.
+    fn find(&self, history: &VecDeque<Snapshot>, after: Option<u64>) -> Option<u64> {
+        let (first, rest) = self.steps.split_first()?;
+        let newest = history.back()?;
+
+        // For each snapshot, the latest start of a match of the steps so far
+        // that ends on it. Any earlier occurrence of a step may be the one
+        // that keeps the next step within the leniency.
+        let mut starts: Vec<Option<u64>> = history
+            .iter()
+            .map(|snapshot| {
+                let in_window = newest.tick - snapshot.tick <= self.window;
+                let fresh = after.is_none_or(|after| snapshot.tick > after);
+                (in_window && fresh && first.matches(snapshot)).then_some(snapshot.tick)
+            })
+            .collect();
+        for step in rest {
+            starts = history
+                .iter()
+                .enumerate()
+                .map(|(index, snapshot)| {
+                    if !step.matches(snapshot) {
+                        return None;
+                    }
+                    history
+                        .iter()
+                        .zip(&starts)
+                        .take(index)
+                        .filter(|(earlier, _)| snapshot.tick - earlier.tick <= self.leniency)
+                        .filter_map(|(_, start)| *start)
+                        .max()
+                })
+                .collect();
+        }
+        *starts.last()?
+    }
+}
+
+/// State of the actions used by combos at one tick.
+#[derive(Debug)]
+struct Snapshot {
+    tick: u64,
+    pressed: HashSet<String>,
+    /// Consecutive ticks each held action has been held for.
+    held: HashMap<String, u32>,
+}
+
+/// A per-tick history of action presses matched against registered
+/// [`Combo`]s.
+///
+/// [`Input`](super::Input) updates it at the start of every fixed tick, so
+/// timing is measured in ticks and is the same when a recording is replayed.
+#[derive(Debug, Default)]
+pub struct ComboBuffer {
+    combos: Vec<(String, Combo)>,
+    history: VecDeque<Snapshot>,
+    held: HashMap<String, u32>,
+    /// Start tick of the last match of each combo, so one input sequence
+    /// only triggers a combo once.
+    last_start: HashMap<String, u64>,
+    frame: Vec<String>,
+    tick: Vec<String>,
+    phase: InputPhase,
+}
+
+impl ComboBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `combo` as `name`, replacing any combo with that name.
+    pub fn register(&mut self, name: impl Into<String>, combo: Combo) {
+        let name = name.into();
+        self.remove(&name);
+        self.combos.push((name, combo));
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.combos.retain(|(n, _)| n != name);
+        self.last_start.remove(name);
+    }
+
+    /// Forgets all recorded input, e.g. after a round ends.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.held.clear();
+    }
+
+    /// Names of the combos completed this frame, or this tick inside
+    /// `fixed_update`, in registration order.
+    pub fn triggered(&self) -> &[String] {
+        match self.phase {
+            InputPhase::Frame => &self.frame,
+            InputPhase::Tick => &self.tick,
+        }
+    }
+
+    pub fn is_triggered(&self, name: &str) -> bool {
+        self.triggered().iter().any(|n| n == name)
+    }
+
+    /// Records the state of every action used by a combo at `tick` and checks
+    /// for completed combos. `state` returns whether an action is held and
+    /// whether it was just pressed.
+    pub fn update(&mut self, tick: u64, state: impl Fn(&str) -> (bool, bool)) {
+        if self.combos.is_empty() {
+            return;
+        }
+
+        let actions: HashSet<&String> = self
+            .combos
+            .iter()
+            .flat_map(|(_, combo)| &combo.steps)
+            .flat_map(|step| &step.actions)
+            .collect();
+        let mut pressed = HashSet::new();
+        for action in actions {
+            let (held, just_pressed) = state(action);
+            if held {
+                *self.held.entry(action.clone()).or_default() += 1;
+            } else {
+                self.held.remove(action);
+            }
+            if just_pressed {
+                pressed.insert(action.clone());
+            }
+        }
+        self.history.push_back(Snapshot {
+            tick,
+            pressed,
+            held: self.held.clone(),
+        });
+
+        let max_window = self.combos.iter().map(|(_, c)| c.window).max().unwrap_or(0);
+        while let Some(oldest) = self.history.front()
+            && tick - oldest.tick > max_window
+        {
+            self.history.pop_front();
+        }
+
+        for (name, combo) in &self.combos {
+            if let Some(start) = combo.find(&self.history, self.last_start.get(name).copied()) {
+                self.last_start.insert(name.clone(), start);
+                self.frame.push(name.clone());
+                self.tick.push(name.clone());
+            }
+        }
+    }
+
+    pub fn set_phase(&mut self, phase: InputPhase) {
+        self.phase = phase;
+    }
+
+    pub fn end_frame(&mut self) {
+        self.frame.clear();
+    }
+
+    pub fn end_tick(&mut self) {
+        self.tick.clear();
+    }
+}
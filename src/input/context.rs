@@ -0,0 +1,134 @@
+use super::{Input, TextEvent};
+use glam::Vec2;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// A layer of the input context stack, e.g. `"gameplay"`, `"pause_menu"` or
+/// `"console"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputContext {
+    name: String,
+    blocking: bool,
+}
+
+impl InputContext {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            blocking: false,
+        }
+    }
+
+    /// Makes the context modal: contexts below it are inactive while it is on
+    /// the stack. Input held when it is pushed or removed is consumed, so
+    /// presses made while it is on top only ever reach it.
+    pub fn blocking(mut self) -> Self {
+        self.blocking = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.blocking
+    }
+}
+
+/// Input as seen by one context on the stack, from [`Input::context`].
+///
+/// While the context is inactive everything reads as released and at rest,
+/// so a layer can query input without checking the stack itself.
+#[derive(Debug, Clone, Copy)]
+pub struct ContextInput<'a> {
+    input: &'a Input,
+    active: bool,
+}
+
+impl<'a> ContextInput<'a> {
+    pub(super) fn new(input: &'a Input, active: bool) -> Self {
+        Self { input, active }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn key_pressed(&self, key: &KeyCode) -> bool {
+        self.active && self.input.keyboard.is_pressed(key)
+    }
+
+    pub fn key_just_pressed(&self, key: &KeyCode) -> bool {
+        self.active && self.input.keyboard.is_just_pressed(key)
+    }
+
+    pub fn key_just_released(&self, key: &KeyCode) -> bool {
+        self.active && self.input.keyboard.is_just_released(key)
+    }
+
+    pub fn mouse_pressed(&self, button: &MouseButton) -> bool {
+        self.active && self.input.mouse.is_pressed(button)
+    }
+
+    pub fn mouse_just_pressed(&self, button: &MouseButton) -> bool {
+        self.active && self.input.mouse.is_just_pressed(button)
+    }
+
+    pub fn mouse_just_released(&self, button: &MouseButton) -> bool {
+        self.active && self.input.mouse.is_just_released(button)
+    }
+
+    pub fn mouse_delta(&self) -> Vec2 {
+        if self.active {
+            self.input.mouse.delta()
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    pub fn scroll_lines(&self) -> Vec2 {
+        if self.active {
+            self.input.mouse.scroll_lines()
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.active && self.input.action_pressed(action)
+    }
+
+    pub fn action_just_pressed(&self, action: &str) -> bool {
+        self.active && self.input.action_just_pressed(action)
+    }
+
+    pub fn action_just_released(&self, action: &str) -> bool {
+        self.active && self.input.action_just_released(action)
+    }
+
+    pub fn axis(&self, name: &str) -> f32 {
+        if self.active {
+            self.input.axis(name)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn axis_2d(&self, name: &str) -> Vec2 {
+        if self.active {
+            self.input.axis_2d(name)
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    /// Text typed this frame.
+    pub fn text_events(&self) -> &'a [TextEvent] {
+        if self.active {
+            self.input.text.events()
+        } else {
+            &[]
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use super::button::{ButtonState, InputPhase};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Identifies a connected gamepad for as long as it stays connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GamepadId(pub usize);
+
+/// Buttons named by position, so `South` is A on Xbox and Cross on PlayStation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// Analog inputs. Sticks range over -1..1 with y up, triggers over 0..1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    const COUNT: usize = 6;
+
+    /// Consumed axes stay hidden until they come back within this of rest.
+    const REST: f32 = 0.1;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GamepadEvent {
+    Connected {
+        id: GamepadId,
+        name: String,
+    },
+    Disconnected {
+        id: GamepadId,
+    },
+    ButtonPressed {
+        id: GamepadId,
+        button: GamepadButton,
+    },
+    ButtonReleased {
+        id: GamepadId,
+        button: GamepadButton,
+    },
+    AxisChanged {
+        id: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+/// A source of gamepad events, polled once per frame.
+pub trait GamepadBackend {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>);
+}
+
+#[derive(Debug)]
+pub struct Gamepad {
+    id: GamepadId,
+    name: String,
+    buttons: ButtonState<GamepadButton>,
+    axes: [f32; GamepadAxis::COUNT],
+    consumed_axes: [bool; GamepadAxis::COUNT],
+}
+
+impl Gamepad {
+    fn new(id: GamepadId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            buttons: ButtonState::default(),
+            axes: [0.0; GamepadAxis::COUNT],
+            consumed_axes: [false; GamepadAxis::COUNT],
+        }
+    }
+
+    pub fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_pressed(&self, button: &GamepadButton) -> bool {
+        self.buttons.is_pressed(button)
+    }
+
+    pub fn is_just_pressed(&self, button: &GamepadButton) -> bool {
+        self.buttons.is_just_pressed(button)
+    }
+
+    pub fn is_just_released(&self, button: &GamepadButton) -> bool {
+        self.buttons.is_just_released(button)
+    }
+
+    /// Raw value of `axis`. Dead zones are applied by `Axis`/`Axis2D` bindings.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        if self.consumed_axes[axis as usize] {
+            0.0
+        } else {
+            self.axes[axis as usize]
+        }
+    }
+
+    fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes[axis as usize] = value;
+        if value.abs() <= GamepadAxis::REST {
+            self.consumed_axes[axis as usize] = false;
+        }
+    }
+
+    /// Hides every button held and every axis away from rest until they are
+    /// released or return to rest.
+    fn consume_all(&mut self) {
+        self.buttons.consume_all();
+        for (value, consumed) in self.axes.iter().zip(&mut self.consumed_axes) {
+            *consumed |= value.abs() > GamepadAxis::REST;
+        }
+    }
+
+    pub fn left_stick(&self) -> Vec2 {
+        Vec2::new(
+            self.axis(GamepadAxis::LeftStickX),
+            self.axis(GamepadAxis::LeftStickY),
+        )
+    }
+
+    pub fn right_stick(&self) -> Vec2 {
+        Vec2::new(
+            self.axis(GamepadAxis::RightStickX),
+            self.axis(GamepadAxis::RightStickY),
+        )
+    }
+}
+
+/// Every connected gamepad, fed by an optional [`GamepadBackend`].
+#[derive(Default)]
+pub struct Gamepads {
+    pads: BTreeMap<GamepadId, Gamepad>,
+    backend: Option<Box<dyn GamepadBackend>>,
+    /// Events processed since the end of the last frame.
+    events: Vec<GamepadEvent>,
+    phase: InputPhase,
+}
+
+impl std::fmt::Debug for Gamepads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gamepads")
+            .field("pads", &self.pads)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_backend(&mut self, backend: impl GamepadBackend + 'static) {
+        self.backend = Some(Box::new(backend));
+    }
+
+    pub fn has_backend(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    /// Appends new events from the backend to `events` without applying
+    /// them. [`Input::poll_gamepads`](super::Input::poll_gamepads) polls and
+    /// applies in one go.
+    pub fn poll_backend(&mut self, events: &mut Vec<GamepadEvent>) {
+        if let Some(backend) = &mut self.backend {
+            backend.poll(events);
+        }
+    }
+
+    pub fn process_event(&mut self, event: GamepadEvent) {
+        match &event {
+            GamepadEvent::Connected { id, name } => {
+                let mut pad = Gamepad::new(*id, name.clone());
+                pad.buttons.set_phase(self.phase);
+                self.pads.insert(*id, pad);
+            }
+            GamepadEvent::Disconnected { id } => {
+                self.pads.remove(id);
+            }
+            GamepadEvent::ButtonPressed { id, button } => {
+                if let Some(pad) = self.pads.get_mut(id) {
+                    pad.buttons.press(*button);
+                }
+            }
+            GamepadEvent::ButtonReleased { id, button } => {
+                if let Some(pad) = self.pads.get_mut(id) {
+                    pad.buttons.release(*button);
+                }
+            }
+            GamepadEvent::AxisChanged { id, axis, value } => {
+                if let Some(pad) = self.pads.get_mut(id) {
+                    pad.set_axis(*axis, *value);
+                }
+            }
+        }
+        self.events.push(event);
+    }
+
+    pub fn set_phase(&mut self, phase: InputPhase) {
+        self.phase = phase;
+        for pad in self.pads.values_mut() {
+            pad.buttons.set_phase(phase);
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.events.clear();
+        for pad in self.pads.values_mut() {
+            pad.buttons.end_frame();
+        }
+    }
+
+    pub fn end_tick(&mut self) {
+        for pad in self.pads.values_mut() {
+            pad.buttons.end_tick();
+        }
+    }
+
+    pub fn get(&self, id: GamepadId) -> Option<&Gamepad> {
+        self.pads.get(&id)
+    }
+
+    /// Connected gamepads ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = &Gamepad> {
+        self.pads.values()
+    }
+
+    /// The connected gamepad with the lowest id, for single-player games.
+    pub fn first(&self) -> Option<&Gamepad> {
+        self.pads.values().next()
+    }
+
+    /// Connections, disconnections and input received this frame.
+    pub fn events(&self) -> &[GamepadEvent] {
+        &self.events
+    }
+
+    pub fn any_pressed(&self, button: &GamepadButton) -> bool {
+        self.iter().any(|pad| pad.is_pressed(button))
+    }
+
+    pub fn any_just_pressed(&self, button: &GamepadButton) -> bool {
+        self.iter().any(|pad| pad.is_just_pressed(button))
+    }
+
+    pub fn any_just_released(&self, button: &GamepadButton) -> bool {
+        self.iter().any(|pad| pad.is_just_released(button))
+    }
+
+    /// Hides `button` on every gamepad from queries until it is released.
+    pub fn consume(&mut self, button: &GamepadButton) {
+        for pad in self.pads.values_mut() {
+            pad.buttons.consume(button);
+        }
+    }
+
+    /// Hides held buttons and deflected axes on every gamepad until they are
+    /// released or return to rest.
+    pub fn consume_all(&mut self) {
+        for pad in self.pads.values_mut() {
+            pad.consume_all();
+        }
+    }
+
+    /// The value of `axis` furthest from rest across all gamepads.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.iter()
+            .map(|pad| pad.axis(axis))
+            .fold(0.0, |a, b| if b.abs() > a.abs() { b } else { a })
+    }
+}
+
+/// An in-memory backend for tests and tools. Gamepads are created with
+/// [`VirtualGamepads::connect`] and driven through the returned handle.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualGamepads {
+    queue: Arc<Mutex<VecDeque<GamepadEvent>>>,
+    next_id: Arc<Mutex<usize>>,
+}
+
+impl VirtualGamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn connect(&self, name: impl Into<String>) -> VirtualGamepad {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            GamepadId(*next_id - 1)
+        };
+        let pad = VirtualGamepad {
+            id,
+            queue: self.queue.clone(),
+        };
+        pad.send(GamepadEvent::Connected {
+            id,
+            name: name.into(),
+        });
+        pad
+    }
+}
+
+impl GamepadBackend for VirtualGamepads {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        events.extend(self.queue.lock().unwrap().drain(..));
+    }
+}
+
+/// Handle to a gamepad of a [`VirtualGamepads`] backend. Changes are seen on
+/// the next [`Input::poll_gamepads`](super::Input::poll_gamepads).
+#[derive(Debug, Clone)]
+pub struct VirtualGamepad {
+    id: GamepadId,
+    queue: Arc<Mutex<VecDeque<GamepadEvent>>>,
+}
+
+impl VirtualGamepad {
+    pub fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    pub fn press(&self, button: GamepadButton) {
+        self.send(GamepadEvent::ButtonPressed {
+            id: self.id,
+            button,
+        });
+    }
+
+    pub fn release(&self, button: GamepadButton) {
+        self.send(GamepadEvent::ButtonReleased {
+            id: self.id,
+            button,
+        });
+    }
+
+    pub fn set_axis(&self, axis: GamepadAxis, value: f32) {
+        self.send(GamepadEvent::AxisChanged {
+            id: self.id,
+            axis,
+            value,
+        });
+    }
+
+    pub fn disconnect(self) {
+        self.send(GamepadEvent::Disconnected { id: self.id });
+    }
+
+    fn send(&self, event: GamepadEvent) {
+        self.queue.lock().unwrap().push_back(event);
+    }
+}
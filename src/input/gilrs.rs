@@ -0,0 +1,150 @@
+use super::gamepad::{GamepadAxis, GamepadBackend, GamepadButton, GamepadEvent, GamepadId};
+use gilrs::{Axis, Button, EventType, Gilrs};
+use tracing::warn;
+
+/// Gamepad backend for real hardware, built on gilrs.
+pub struct GilrsBackend {
+    gilrs: Gilrs,
+    /// Connections for pads that were plugged in before the backend was
+    /// created, which gilrs doesn't send events for.
+    connected: Vec<GamepadEvent>,
+}
+
+impl GilrsBackend {
+    /// Returns `None` if the platform's gamepad API is unavailable.
+    pub fn new() -> Option<Self> {
+        match Gilrs::new() {
+            Ok(gilrs) => {
+                let connected = gilrs
+                    .gamepads()
+                    .map(|(id, pad)| GamepadEvent::Connected {
+                        id: GamepadId(usize::from(id)),
+                        name: pad.name().to_string(),
+                    })
+                    .collect();
+                Some(Self { gilrs, connected })
+            }
+            Err(e) => {
+                warn!("Gamepad support unavailable: {}", e);
+                None
+            }
+        }
+    }
+}
+
+impl GamepadBackend for GilrsBackend {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        events.append(&mut self.connected);
+        while let Some(event) = self.gilrs.next_event() {
+            let id = GamepadId(usize::from(event.id));
+            match event.event {
+                EventType::Connected => events.push(GamepadEvent::Connected {
+                    id,
+                    name: self.gilrs.gamepad(event.id).name().to_string(),
+                }),
+                EventType::Disconnected => events.push(GamepadEvent::Disconnected { id }),
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = map_button(button) {
+                        events.push(GamepadEvent::ButtonPressed { id, button });
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = map_button(button) {
+                        events.push(GamepadEvent::ButtonReleased { id, button });
+                    }
+                }
+                // Analog triggers are reported as button values.
+                EventType::ButtonChanged(Button::LeftTrigger2, value, _) => {
+                    events.push(GamepadEvent::AxisChanged {
+                        id,
+                        axis: GamepadAxis::LeftTrigger,
+                        value,
+                    })
+                }
+                EventType::ButtonChanged(Button::RightTrigger2, value, _) => {
+                    events.push(GamepadEvent::AxisChanged {
+                        id,
+                        axis: GamepadAxis::RightTrigger,
+                        value,
+                    })
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(axis) = map_axis(axis) {
+                        events.push(GamepadEvent::AxisChanged { id, axis, value });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn map_button(button: Button) -> Option<GamepadButton> {
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::Mode => GamepadButton::Mode,
+        Button::LeftThumb => GamepadButton::LeftStick,
+        Button::RightThumb => GamepadButton::RightStick,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+    Some(match axis {
+        Axis::LeftStickX => GamepadAxis::LeftStickX,
+        Axis::LeftStickY => GamepadAxis::LeftStickY,
+        Axis::RightStickX => GamepadAxis::RightStickX,
+        Axis::RightStickY => GamepadAxis::RightStickY,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buttons_map_by_position() {
+        assert_eq!(map_button(Button::South), Some(GamepadButton::South));
+        assert_eq!(
+            map_button(Button::LeftTrigger),
+            Some(GamepadButton::LeftBumper)
+        );
+        assert_eq!(
+            map_button(Button::LeftTrigger2),
+            Some(GamepadButton::LeftTrigger)
+        );
+        assert_eq!(
+            map_button(Button::LeftThumb),
+            Some(GamepadButton::LeftStick)
+        );
+        assert_eq!(
+            map_button(Button::DPadRight),
+            Some(GamepadButton::DPadRight)
+        );
+        assert_eq!(map_button(Button::C), None);
+        assert_eq!(map_button(Button::Unknown), None);
+    }
+
+    #[test]
+    fn stick_axes_map_and_others_are_dropped() {
+        assert_eq!(map_axis(Axis::LeftStickX), Some(GamepadAxis::LeftStickX));
+        assert_eq!(map_axis(Axis::RightStickY), Some(GamepadAxis::RightStickY));
+        assert_eq!(map_axis(Axis::LeftZ), None);
+        assert_eq!(map_axis(Axis::DPadX), None);
+        assert_eq!(map_axis(Axis::Unknown), None);
+    }
+}
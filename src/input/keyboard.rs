@@ -1,12 +1,18 @@
-use std::collections::HashSet;
+use super::button::{ButtonState, InputPhase};
+use super::shortcut::Shortcut;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use winit::event::{ElementState, KeyEvent};
-use winit::keyboard::{Key, KeyCode, PhysicalKey};
+use winit::keyboard::{Key, KeyCode, ModifiersState, PhysicalKey};
 
 #[derive(Default, Debug)]
 pub struct Keyboard {
-    curr_pressed: HashSet<KeyCode>,
-    just_pressed: HashSet<KeyCode>,
-    just_released: HashSet<KeyCode>,
+    keys: ButtonState<KeyCode>,
+    logical: ButtonState<Key>,
+    /// Logical key produced by each held physical key, so the release matches
+    /// the press even if modifiers changed in between.
+    held_logical: HashMap<PhysicalKey, Key>,
+    modifiers: ModifiersState,
 }
 
 impl Keyboard {
@@ -14,35 +20,128 @@ impl Keyboard {
         Self::default()
     }
 
-    pub fn update(&mut self) {
-        self.just_released.clear();
-        self.just_released.clear();
+    pub fn set_phase(&mut self, phase: InputPhase) {
+        self.keys.set_phase(phase);
+        self.logical.set_phase(phase);
+    }
+
+    pub fn end_frame(&mut self) {
+        self.keys.end_frame();
+        self.logical.end_frame();
+    }
+
+    pub fn end_tick(&mut self) {
+        self.keys.end_tick();
+        self.logical.end_tick();
     }
 
     pub fn process_event(&mut self, event: &KeyEvent) {
         if let PhysicalKey::Code(keycode) = event.physical_key {
-            match event.state {
-                ElementState::Pressed => {
-                    self.curr_pressed.insert(keycode);
-                    self.just_pressed.insert(keycode);
-                }
-                ElementState::Released => {
-                    self.curr_pressed.remove(&keycode);
-                    self.just_released.insert(keycode);
+            self.process_key(keycode, event.state);
+        }
+        self.process_logical_key(event.physical_key, event.logical_key.clone(), event.state);
+    }
+
+    /// Applies a press or release of `key`, as [`Keyboard::process_event`]
+    /// does for window events.
+    pub fn process_key(&mut self, key: KeyCode, state: ElementState) {
+        match state {
+            ElementState::Pressed => self.keys.press(key),
+            ElementState::Released => self.keys.release(key),
+        }
+    }
+
+    /// Applies a press or release of the logical key `key` produced by
+    /// `physical`.
+    pub fn process_logical_key(&mut self, physical: PhysicalKey, key: Key, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                if let Entry::Vacant(entry) = self.held_logical.entry(physical) {
+                    entry.insert(key.clone());
+                    self.logical.press(key);
                 }
             }
+            ElementState::Released => {
+                let key = self.held_logical.remove(&physical).unwrap_or(key);
+                self.logical.release(key);
+            }
         }
     }
 
+    /// Releases every held key and clears the modifiers.
+    pub fn release_all(&mut self) {
+        self.keys.release_all();
+        self.logical.release_all();
+        self.held_logical.clear();
+        self.modifiers = ModifiersState::empty();
+    }
+
+    /// Hides `key` and the logical key it produced from queries until it is
+    /// released.
+    pub fn consume(&mut self, key: &KeyCode) {
+        self.keys.consume(key);
+        if let Some(logical) = self.held_logical.get(&PhysicalKey::Code(*key)) {
+            self.logical.consume(logical);
+        }
+    }
+
+    /// Hides the logical `key` and the physical keys producing it from
+    /// queries until they are released.
+    pub fn consume_logical(&mut self, key: &Key) {
+        self.logical.consume(key);
+        for (physical, logical) in &self.held_logical {
+            if logical == key
+                && let PhysicalKey::Code(code) = physical
+            {
+                self.keys.consume(code);
+            }
+        }
+    }
+
+    pub fn consume_all(&mut self) {
+        self.keys.consume_all();
+        self.logical.consume_all();
+    }
+
+    /// Updates the modifier state, fed from `WindowEvent::ModifiersChanged`.
+    pub fn set_modifiers(&mut self, modifiers: ModifiersState) {
+        self.modifiers = modifiers;
+    }
+
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
     pub fn is_pressed(&self, key: &KeyCode) -> bool {
-        self.curr_pressed.contains(key)
+        self.keys.is_pressed(key)
     }
 
     pub fn is_just_pressed(&self, key: &KeyCode) -> bool {
-        self.just_pressed.contains(key)
+        self.keys.is_just_pressed(key)
     }
 
     pub fn is_just_released(&self, key: &KeyCode) -> bool {
-        self.just_released.contains(key)
+        self.keys.is_just_released(key)
+    }
+
+    /// Whether the layout-dependent `key` is held, e.g. `Key::Character("z")`.
+    pub fn is_logical_pressed(&self, key: &Key) -> bool {
+        self.logical.is_pressed(key)
+    }
+
+    pub fn is_logical_just_pressed(&self, key: &Key) -> bool {
+        self.logical.is_just_pressed(key)
+    }
+
+    pub fn is_logical_just_released(&self, key: &Key) -> bool {
+        self.logical.is_just_released(key)
+    }
+
+    /// Whether `shortcut` was triggered: its key was just pressed while
+    /// exactly its modifiers are held.
+    pub fn is_shortcut_just_pressed(&self, shortcut: &Shortcut) -> bool {
+        self.logical
+            .just_pressed()
+            .any(|key| shortcut.matches(self.modifiers, key))
     }
 }
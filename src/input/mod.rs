@@ -1,18 +1,464 @@
+mod action;
+mod axis;
+mod button;
+mod combo;
+mod context;
+mod gamepad;
+#[cfg(feature = "gilrs")]
+mod gilrs;
 mod keyboard;
+mod mouse;
+mod record;
+mod shortcut;
+mod text;
+mod touch;
 
-use keyboard::Keyboard;
+pub use action::{ActionMap, Binding};
+pub use axis::{Axis, Axis2D, AxisSettings, AxisSource, ResponseCurve};
+pub use button::InputPhase;
+pub use combo::{Combo, ComboBuffer, ComboStep};
+pub use context::{ContextInput, InputContext};
+pub use gamepad::{
+    Gamepad, GamepadAxis, GamepadBackend, GamepadButton, GamepadEvent, GamepadId, Gamepads,
+    VirtualGamepad, VirtualGamepads,
+};
+#[cfg(feature = "gilrs")]
+pub use gilrs::GilrsBackend;
+pub use keyboard::Keyboard;
+pub use mouse::{CursorMode, CursorRequest, Mouse};
+pub use record::{InputEvent, RecordedEvent, Recording};
+pub use shortcut::Shortcut;
+pub use text::{EditCommand, ImeRequest, TextEvent, TextInput};
+pub use touch::{Gesture, GestureSettings, SwipeDirection, Touch, Touches};
 
+use glam::Vec2;
+use record::{Playback, Recorder};
+use tracing::info;
+use winit::event::ElementState;
+use winit::keyboard::{Key, KeyCode, NamedKey, PhysicalKey};
+
+#[derive(Debug)]
 pub struct Input {
     pub keyboard: Keyboard,
+    pub mouse: Mouse,
+    pub text: TextInput,
+    pub touches: Touches,
+    pub gamepads: Gamepads,
+    pub actions: ActionMap,
+    pub combos: ComboBuffer,
+    contexts: Vec<InputContext>,
+    focused: bool,
+    focus_changed: Option<bool>,
+    tick: u64,
+    recorder: Option<Recorder>,
+    playback: Option<Playback>,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Input {
     pub fn new() -> Input {
         Input {
             keyboard: Keyboard::new(),
+            mouse: Mouse::new(),
+            text: TextInput::new(),
+            touches: Touches::new(),
+            gamepads: Gamepads::new(),
+            actions: ActionMap::new(),
+            combos: ComboBuffer::new(),
+            contexts: Vec::new(),
+            focused: true,
+            focus_changed: None,
+            tick: 0,
+            recorder: None,
+            playback: None,
+        }
+    }
+    /// Switches queries to the fixed-tick edge buffers. Call before each
+    /// simulation step.
+    pub fn begin_tick(&mut self) {
+        self.replay();
+        self.set_phase(InputPhase::Tick);
+
+        let mut combos = std::mem::take(&mut self.combos);
+        combos.update(self.tick, |action| {
+            (
+                self.action_pressed(action),
+                self.action_just_pressed(action),
+            )
+        });
+        self.combos = combos;
+    }
+
+    /// Forgets the edges seen by the step that just ran and switches queries
+    /// back to the per-frame buffers.
+    pub fn end_tick(&mut self) {
+        self.keyboard.end_tick();
+        self.mouse.end_tick();
+        self.gamepads.end_tick();
+        self.combos.end_tick();
+        self.set_phase(InputPhase::Frame);
+        self.tick += 1;
+    }
+
+    /// Number of fixed ticks that have completed.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Forgets the edges seen by this frame's `update` and `render`.
+    pub fn end_frame(&mut self) {
+        self.keyboard.end_frame();
+        self.mouse.end_frame();
+        self.gamepads.end_frame();
+        self.combos.end_frame();
+        self.text.end_frame();
+        self.touches.end_frame();
+        self.focus_changed = None;
+    }
+
+    /// Whether the window has keyboard focus.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// The new focus state if the window gained or lost focus this frame.
+    pub fn focus_changed(&self) -> Option<bool> {
+        self.focus_changed
+    }
+
+    /// Applies a live event, recording it first if a recording is running.
+    /// Live events are ignored while a recording is being played back.
+    pub fn handle(&mut self, event: InputEvent) {
+        if self.playback.is_some() {
+            return;
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.push(self.tick, event.clone());
+        }
+        self.apply(&event);
+    }
+
+    /// Pulls new events from the gamepad backend and handles them. Returns
+    /// whether there were any.
+    pub fn poll_gamepads(&mut self) -> bool {
+        let mut events = Vec::new();
+        self.gamepads.poll_backend(&mut events);
+        let any = !events.is_empty();
+        for event in events {
+            self.handle(InputEvent::Gamepad(event));
+        }
+        any
+    }
+
+    /// Starts recording every handled event, replacing any recording in progress.
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder::new(self.tick));
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recorder.take().map(Recorder::finish)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Replays `recording` in place of live events, starting with the next
+    /// tick. Each event is applied right before the tick it was recorded
+    /// before; live input resumes once the recording runs out.
+    pub fn start_playback(&mut self, recording: Recording) {
+        self.playback = Some(Playback::new(recording, self.tick));
+    }
+
+    pub fn is_playing_back(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    fn replay(&mut self) {
+        let Some(mut playback) = self.playback.take() else {
+            return;
+        };
+        while let Some(event) = playback.next(self.tick) {
+            self.apply(&event);
+        }
+        if playback.is_finished() {
+            info!("Input playback finished at tick {}", self.tick);
+        } else {
+            self.playback = Some(playback);
+        }
+    }
+
+    fn apply(&mut self, event: &InputEvent) {
+        match event {
+            InputEvent::Key {
+                physical,
+                logical,
+                text,
+                state,
+            } => {
+                if let PhysicalKey::Code(key) = physical {
+                    self.keyboard.process_key(*key, *state);
+                }
+                self.keyboard
+                    .process_logical_key(*physical, logical.clone(), *state);
+                self.text.process_key(logical, text.as_deref(), *state);
+                if *logical == Key::Named(NamedKey::Escape)
+                    && *state == ElementState::Pressed
+                    && self.mouse.release_on_escape()
+                {
+                    self.mouse.release_cursor();
+                }
+            }
+            InputEvent::Modifiers(modifiers) => self.keyboard.set_modifiers(*modifiers),
+            InputEvent::Ime(ime) => self.text.process_ime(ime),
+            InputEvent::CursorMoved(position) => self.mouse.process_cursor_moved(*position),
+            InputEvent::CursorEntered => self.mouse.process_cursor_inside(true),
+            InputEvent::CursorLeft => self.mouse.process_cursor_inside(false),
+            InputEvent::MouseButton { button, state } => self.mouse.process_button(*button, *state),
+            InputEvent::MouseWheel(delta) => self.mouse.process_scroll(*delta),
+            InputEvent::MouseMotion { dx, dy } => self.mouse.process_motion(*dx, *dy),
+            InputEvent::Gamepad(event) => self.gamepads.process_event(event.clone()),
+            InputEvent::Focused(focused) => self.set_focused(*focused),
+            InputEvent::Touch {
+                id,
+                phase,
+                position,
+                pressure,
+            } => self.touches.process_touch(
+                *id,
+                *phase,
+                Vec2::new(position.x as f32, position.y as f32),
+                pressure.map(|p| p as f32),
+            ),
+            InputEvent::PinchGesture(delta) => {
+                let center = self.mouse_position();
+                self.touches.process_pinch(1.0 + *delta as f32, center);
+            }
+            InputEvent::PanGesture(delta) => {
+                self.touches.process_pan(Vec2::new(delta.x, delta.y));
+            }
+            InputEvent::DoubleTapGesture => {
+                let position = self.mouse_position();
+                self.touches.process_double_tap(position);
+            }
+        }
+    }
+
+    /// Releases held keys and mouse buttons on focus loss, since their
+    /// releases go to whichever window has focus now.
+    fn set_focused(&mut self, focused: bool) {
+        if focused == self.focused {
+            return;
+        }
+        if !focused {
+            self.keyboard.release_all();
+            self.mouse.release_all();
+            self.mouse.release_cursor();
+            self.touches.cancel_all();
+        }
+        self.focused = focused;
+        self.focus_changed = Some(focused);
+    }
+
+    /// Trackpad gestures happen under the cursor.
+    fn mouse_position(&self) -> Vec2 {
+        let position = self.mouse.position();
+        Vec2::new(position.x as f32, position.y as f32)
+    }
+
+    fn set_phase(&mut self, phase: InputPhase) {
+        self.keyboard.set_phase(phase);
+        self.mouse.set_phase(phase);
+        self.gamepads.set_phase(phase);
+        self.combos.set_phase(phase);
+    }
+
+    /// Pushes `context` on top of the stack. Pushing a blocking context
+    /// consumes everything currently held.
+    pub fn push_context(&mut self, context: InputContext) {
+        if context.is_blocking() {
+            self.consume_all();
         }
+        self.contexts.push(context);
     }
-    pub fn update(&mut self) {
-        self.keyboard.update();
+
+    /// Pops the top context. Popping a blocking context consumes everything
+    /// currently held, so input meant for it doesn't fall through.
+    pub fn pop_context(&mut self) -> Option<InputContext> {
+        let context = self.contexts.pop()?;
+        if context.is_blocking() {
+            self.consume_all();
+        }
+        Some(context)
+    }
+
+    /// Removes the topmost context called `name`, wherever it is in the
+    /// stack, consuming held input like [`Input::pop_context`].
+    pub fn remove_context(&mut self, name: &str) -> Option<InputContext> {
+        let index = self.contexts.iter().rposition(|c| c.name() == name)?;
+        let context = self.contexts.remove(index);
+        if context.is_blocking() {
+            self.consume_all();
+        }
+        Some(context)
+    }
+
+    /// Contexts from bottom to top.
+    pub fn contexts(&self) -> &[InputContext] {
+        &self.contexts
+    }
+
+    pub fn top_context(&self) -> Option<&InputContext> {
+        self.contexts.last()
+    }
+
+    /// Whether `name` is on the stack with no blocking context above it.
+    pub fn is_context_active(&self, name: &str) -> bool {
+        match self.contexts.iter().rposition(|c| c.name() == name) {
+            Some(index) => !self.contexts[index + 1..]
+                .iter()
+                .any(InputContext::is_blocking),
+            None => false,
+        }
+    }
+
+    /// Input as seen by the context called `name`: nothing is pressed while
+    /// it is inactive. Queries on `Input` itself ignore the stack.
+    pub fn context(&self, name: &str) -> ContextInput<'_> {
+        ContextInput::new(self, self.is_context_active(name))
+    }
+
+    /// Hides `key` from lower layers until it is released.
+    pub fn consume_key(&mut self, key: &KeyCode) {
+        self.keyboard.consume(key);
+    }
+
+    /// Hides every binding of `action` from lower layers until released.
+    pub fn consume_action(&mut self, action: &str) {
+        for binding in self.actions.bindings(action).to_vec() {
+            match binding {
+                Binding::Key(key) => self.keyboard.consume(&key),
+                Binding::Mouse(button) => self.mouse.consume_button(&button),
+                Binding::Gamepad(button) => self.gamepads.consume(&button),
+            }
+        }
+    }
+
+    /// Hides everything held, this frame's edges, mouse motion, scrolling,
+    /// text and deflected gamepad axes from lower layers, e.g. when a UI
+    /// element under the cursor handled the input.
+    pub fn consume_all(&mut self) {
+        self.keyboard.consume_all();
+        self.mouse.consume_all();
+        self.gamepads.consume_all();
+        self.text.consume_all();
+    }
+
+    /// Whether any binding of `action` is held.
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.actions
+            .bindings(action)
+            .iter()
+            .any(|binding| self.binding_pressed(binding))
+    }
+
+    /// Whether any binding of `action` was pressed this frame.
+    pub fn action_just_pressed(&self, action: &str) -> bool {
+        self.actions
+            .bindings(action)
+            .iter()
+            .any(|binding| self.binding_just_pressed(binding))
+    }
+
+    /// Whether a binding of `action` was released this frame and none is still held.
+    pub fn action_just_released(&self, action: &str) -> bool {
+        let bindings = self.actions.bindings(action);
+        bindings
+            .iter()
+            .any(|binding| self.binding_just_released(binding))
+            && !bindings.iter().any(|binding| self.binding_pressed(binding))
+    }
+
+    /// Whether `shortcut` was just triggered. Parse it once up front with
+    /// [`Shortcut::parse`], e.g. from `"Ctrl+Shift+S"`.
+    pub fn shortcut(&self, shortcut: &Shortcut) -> bool {
+        self.keyboard.is_shortcut_just_pressed(shortcut)
+    }
+
+    /// Current value of the named 1D axis, or 0 if it is not bound.
+    pub fn axis(&self, name: &str) -> f32 {
+        let Some(axis) = self.actions.axis(name) else {
+            return 0.0;
+        };
+        let (bounded, unbounded) = self.combine(&axis.sources);
+        axis.settings.apply(bounded.clamp(-1.0, 1.0)) + unbounded * axis.settings.sensitivity
+    }
+
+    /// Current value of the named 2D axis, or zero if it is not bound.
+    pub fn axis_2d(&self, name: &str) -> Vec2 {
+        let Some(axis) = self.actions.axis_2d(name) else {
+            return Vec2::ZERO;
+        };
+        let (bounded_x, unbounded_x) = self.combine(&axis.x);
+        let (bounded_y, unbounded_y) = self.combine(&axis.y);
+        let bounded = Vec2::new(bounded_x, bounded_y).clamp_length_max(1.0);
+        axis.settings.apply_2d(bounded)
+            + Vec2::new(unbounded_x, unbounded_y) * axis.settings.sensitivity
+    }
+
+    /// Sums `sources`, returning the bounded and unbounded parts separately.
+    fn combine(&self, sources: &[AxisSource]) -> (f32, f32) {
+        sources
+            .iter()
+            .fold((0.0, 0.0), |(bounded, unbounded), source| {
+                let value = self.source_value(source);
+                if source.is_bounded() {
+                    (bounded + value, unbounded)
+                } else {
+                    (bounded, unbounded + value)
+                }
+            })
+    }
+
+    fn source_value(&self, source: &AxisSource) -> f32 {
+        match source {
+            AxisSource::Keys { negative, positive } => {
+                let negative = self.keyboard.is_pressed(negative) as i32 as f32;
+                let positive = self.keyboard.is_pressed(positive) as i32 as f32;
+                positive - negative
+            }
+            AxisSource::MouseX => self.mouse.delta().x,
+            AxisSource::MouseY => self.mouse.delta().y,
+            AxisSource::Gamepad(axis) => self.gamepads.axis(*axis),
+        }
+    }
+
+    fn binding_pressed(&self, binding: &Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keyboard.is_pressed(key),
+            Binding::Mouse(button) => self.mouse.is_pressed(button),
+            Binding::Gamepad(button) => self.gamepads.any_pressed(button),
+        }
+    }
+
+    fn binding_just_pressed(&self, binding: &Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keyboard.is_just_pressed(key),
+            Binding::Mouse(button) => self.mouse.is_just_pressed(button),
+            Binding::Gamepad(button) => self.gamepads.any_just_pressed(button),
+        }
+    }
+
+    fn binding_just_released(&self, binding: &Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keyboard.is_just_released(key),
+            Binding::Mouse(button) => self.mouse.is_just_released(button),
+            Binding::Gamepad(button) => self.gamepads.any_just_released(button),
+        }
     }
 }
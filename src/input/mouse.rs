@@ -0,0 +1,287 @@
+use super::button::{ButtonState, InputPhase};
+use glam::Vec2;
+use winit::dpi::{LogicalPosition, PhysicalPosition};
+use winit::event::{DeviceEvent, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+
+/// A value summed over a frame and, separately, over a fixed tick.
+#[derive(Debug, Default, Clone, Copy)]
+struct Accumulated {
+    frame: Vec2,
+    tick: Vec2,
+}
+
+impl Accumulated {
+    fn add(&mut self, value: Vec2) {
+        self.frame += value;
+        self.tick += value;
+    }
+
+    fn get(&self, phase: InputPhase) -> Vec2 {
+        match phase {
+            InputPhase::Frame => self.frame,
+            InputPhase::Tick => self.tick,
+        }
+    }
+}
+
+/// How the cursor is tied to the window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CursorMode {
+    #[default]
+    Normal,
+    /// Kept inside the window.
+    Confined,
+    /// Held in place so only [`Mouse::delta`] changes, for first-person
+    /// cameras. Falls back to `Confined` where locking is unsupported.
+    Locked,
+}
+
+/// Cursor settings requested by game code, applied by `Application`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorRequest {
+    pub mode: CursorMode,
+    pub visible: bool,
+}
+
+#[derive(Debug)]
+pub struct Mouse {
+    position: PhysicalPosition<f64>,
+    scale_factor: f64,
+    delta: Accumulated,
+    scroll_lines: Accumulated,
+    scroll_pixels: Accumulated,
+    inside: bool,
+    buttons: ButtonState<MouseButton>,
+    phase: InputPhase,
+    cursor_mode: CursorMode,
+    cursor_visible: bool,
+    release_on_escape: bool,
+    request_pending: bool,
+}
+
+impl Default for Mouse {
+    fn default() -> Self {
+        Self {
+            position: PhysicalPosition::new(0.0, 0.0),
+            scale_factor: 1.0,
+            delta: Accumulated::default(),
+            scroll_lines: Accumulated::default(),
+            scroll_pixels: Accumulated::default(),
+            inside: false,
+            buttons: ButtonState::default(),
+            phase: InputPhase::default(),
+            cursor_mode: CursorMode::Normal,
+            cursor_visible: true,
+            release_on_escape: true,
+            request_pending: false,
+        }
+    }
+}
+
+impl Mouse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_phase(&mut self, phase: InputPhase) {
+        self.phase = phase;
+        self.buttons.set_phase(phase);
+    }
+
+    pub fn end_frame(&mut self) {
+        self.buttons.end_frame();
+        self.delta.frame = Vec2::ZERO;
+        self.scroll_lines.frame = Vec2::ZERO;
+        self.scroll_pixels.frame = Vec2::ZERO;
+    }
+
+    pub fn end_tick(&mut self) {
+        self.buttons.end_tick();
+        self.delta.tick = Vec2::ZERO;
+        self.scroll_lines.tick = Vec2::ZERO;
+        self.scroll_pixels.tick = Vec2::ZERO;
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+    }
+
+    pub fn process_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => self.process_cursor_moved(*position),
+            WindowEvent::CursorEntered { .. } => self.process_cursor_inside(true),
+            WindowEvent::CursorLeft { .. } => self.process_cursor_inside(false),
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.process_button(*button, *state);
+            }
+            WindowEvent::MouseWheel { delta, .. } => self.process_scroll(*delta),
+            _ => {}
+        }
+    }
+
+    /// Accumulates raw motion, which keeps arriving when the cursor is
+    /// clamped at the edge of the screen.
+    pub fn process_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
+            self.process_motion(*x, *y);
+        }
+    }
+
+    /// Ignored while the cursor is locked, where platforms may warp it back
+    /// to the centre.
+    pub fn process_cursor_moved(&mut self, position: PhysicalPosition<f64>) {
+        if self.cursor_mode != CursorMode::Locked {
+            self.position = position;
+        }
+    }
+
+    pub fn process_cursor_inside(&mut self, inside: bool) {
+        self.inside = inside;
+    }
+
+    pub fn process_scroll(&mut self, delta: MouseScrollDelta) {
+        match delta {
+            MouseScrollDelta::LineDelta(x, y) => {
+                self.scroll_lines.add(Vec2::new(x, y));
+            }
+            MouseScrollDelta::PixelDelta(position) => {
+                self.scroll_pixels
+                    .add(Vec2::new(position.x as f32, position.y as f32));
+            }
+        }
+    }
+
+    pub fn process_motion(&mut self, x: f64, y: f64) {
+        self.delta.add(Vec2::new(x as f32, y as f32));
+    }
+
+    /// Applies a press or release of `button`, as [`Mouse::process_event`]
+    /// does for window events.
+    pub fn process_button(&mut self, button: MouseButton, state: ElementState) {
+        match state {
+            ElementState::Pressed => self.buttons.press(button),
+            ElementState::Released => self.buttons.release(button),
+        }
+    }
+
+    pub fn release_all(&mut self) {
+        self.buttons.release_all();
+    }
+
+    /// Hides `button` from queries until it is released.
+    pub fn consume_button(&mut self, button: &MouseButton) {
+        self.buttons.consume(button);
+    }
+
+    /// Consumes every held button and the motion and scrolling accumulated
+    /// so far.
+    pub fn consume_all(&mut self) {
+        self.buttons.consume_all();
+        self.delta = Accumulated::default();
+        self.scroll_lines = Accumulated::default();
+        self.scroll_pixels = Accumulated::default();
+    }
+
+    pub fn set_cursor_mode(&mut self, mode: CursorMode) {
+        if mode != self.cursor_mode {
+            self.cursor_mode = mode;
+            self.request_pending = true;
+        }
+    }
+
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        if visible != self.cursor_visible {
+            self.cursor_visible = visible;
+            self.request_pending = true;
+        }
+    }
+
+    /// Locks and hides the cursor for relative mouse control. Released again
+    /// when the window loses focus or, unless disabled with
+    /// [`Mouse::set_release_on_escape`], when Escape is pressed.
+    pub fn grab_cursor(&mut self) {
+        self.set_cursor_mode(CursorMode::Locked);
+        self.set_cursor_visible(false);
+    }
+
+    /// Frees and shows the cursor.
+    pub fn release_cursor(&mut self) {
+        self.set_cursor_mode(CursorMode::Normal);
+        self.set_cursor_visible(true);
+    }
+
+    pub fn set_release_on_escape(&mut self, release: bool) {
+        self.release_on_escape = release;
+    }
+
+    pub fn release_on_escape(&self) -> bool {
+        self.release_on_escape
+    }
+
+    pub fn cursor_mode(&self) -> CursorMode {
+        self.cursor_mode
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    /// Whether the cursor is locked, so only relative motion is meaningful.
+    pub fn is_relative(&self) -> bool {
+        self.cursor_mode == CursorMode::Locked
+    }
+
+    /// Returns the cursor settings to apply to the window if they changed.
+    pub fn take_cursor_request(&mut self) -> Option<CursorRequest> {
+        if !self.request_pending {
+            return None;
+        }
+        self.request_pending = false;
+        Some(CursorRequest {
+            mode: self.cursor_mode,
+            visible: self.cursor_visible,
+        })
+    }
+
+    /// Cursor position in physical pixels relative to the top-left of the window.
+    pub fn position(&self) -> PhysicalPosition<f64> {
+        self.position
+    }
+
+    pub fn logical_position(&self) -> LogicalPosition<f64> {
+        self.position.to_logical(self.scale_factor)
+    }
+
+    /// Raw motion since the last frame, or since the last tick inside
+    /// `fixed_update`.
+    pub fn delta(&self) -> Vec2 {
+        self.delta.get(self.phase)
+    }
+
+    /// Wheel movement in lines, as reported by most mice.
+    pub fn scroll_lines(&self) -> Vec2 {
+        self.scroll_lines.get(self.phase)
+    }
+
+    /// Wheel movement in pixels, as reported by touchpads.
+    pub fn scroll_pixels(&self) -> Vec2 {
+        self.scroll_pixels.get(self.phase)
+    }
+
+    /// Whether the cursor is currently over the window.
+    pub fn is_inside(&self) -> bool {
+        self.inside
+    }
+
+    pub fn is_pressed(&self, button: &MouseButton) -> bool {
+        self.buttons.is_pressed(button)
+    }
+
+    pub fn is_just_pressed(&self, button: &MouseButton) -> bool {
+        self.buttons.is_just_pressed(button)
+    }
+
+    pub fn is_just_released(&self, button: &MouseButton) -> bool {
+        self.buttons.is_just_released(button)
+    }
+}
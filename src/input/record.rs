@@ -0,0 +1,212 @@
+use super::gamepad::GamepadEvent;
+use crate::core::EngineError;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use winit::dpi::PhysicalPosition;
+use winit::event::{
+    DeviceEvent, ElementState, Ime, KeyEvent, MouseButton, MouseScrollDelta, TouchPhase,
+    WindowEvent,
+};
+use winit::keyboard::{Key, ModifiersState, PhysicalKey};
+
+const MAGIC: &[u8; 4] = b"INPR";
+const VERSION: u8 = 1;
+
+/// Everything that changes [`Input`](super::Input) state, in a form that can
+/// be recorded and replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputEvent {
+    Key {
+        physical: PhysicalKey,
+        logical: Key,
+        text: Option<String>,
+        state: ElementState,
+    },
+    Modifiers(ModifiersState),
+    Ime(Ime),
+    CursorMoved(PhysicalPosition<f64>),
+    CursorEntered,
+    CursorLeft,
+    MouseButton {
+        button: MouseButton,
+        state: ElementState,
+    },
+    MouseWheel(MouseScrollDelta),
+    MouseMotion {
+        dx: f64,
+        dy: f64,
+    },
+    Gamepad(GamepadEvent),
+    Focused(bool),
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        position: PhysicalPosition<f64>,
+        pressure: Option<f64>,
+    },
+    PinchGesture(f64),
+    PanGesture(PhysicalPosition<f32>),
+    DoubleTapGesture,
+}
+
+impl InputEvent {
+    pub fn from_key_event(event: &KeyEvent) -> Self {
+        InputEvent::Key {
+            physical: event.physical_key,
+            logical: event.logical_key.clone(),
+            text: event.text.as_ref().map(|text| text.to_string()),
+            state: event.state,
+        }
+    }
+
+    /// The input carried by `event`, or `None` for window events that don't
+    /// affect input state.
+    pub fn from_window_event(event: &WindowEvent) -> Option<Self> {
+        Some(match event {
+            WindowEvent::KeyboardInput { event, .. } => Self::from_key_event(event),
+            WindowEvent::ModifiersChanged(modifiers) => InputEvent::Modifiers(modifiers.state()),
+            WindowEvent::Ime(ime) => InputEvent::Ime(ime.clone()),
+            WindowEvent::CursorMoved { position, .. } => InputEvent::CursorMoved(*position),
+            WindowEvent::CursorEntered { .. } => InputEvent::CursorEntered,
+            WindowEvent::CursorLeft { .. } => InputEvent::CursorLeft,
+            WindowEvent::MouseInput { state, button, .. } => InputEvent::MouseButton {
+                button: *button,
+                state: *state,
+            },
+            WindowEvent::MouseWheel { delta, .. } => InputEvent::MouseWheel(*delta),
+            WindowEvent::Focused(focused) => InputEvent::Focused(*focused),
+            WindowEvent::Touch(touch) => InputEvent::Touch {
+                id: touch.id,
+                phase: touch.phase,
+                position: touch.location,
+                pressure: touch.force.map(|force| force.normalized()),
+            },
+            WindowEvent::PinchGesture { delta, .. } => InputEvent::PinchGesture(*delta),
+            WindowEvent::PanGesture { delta, .. } => InputEvent::PanGesture(*delta),
+            WindowEvent::DoubleTapGesture { .. } => InputEvent::DoubleTapGesture,
+            _ => return None,
+        })
+    }
+
+    pub fn from_device_event(event: &DeviceEvent) -> Option<Self> {
+        match event {
+            DeviceEvent::MouseMotion { delta: (dx, dy) } => {
+                Some(InputEvent::MouseMotion { dx: *dx, dy: *dy })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An input event and the fixed tick it arrived before.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub tick: u64,
+    pub event: InputEvent,
+}
+
+/// A sequence of input events tagged with fixed-tick numbers.
+///
+/// Replaying it with [`Input::start_playback`](super::Input::start_playback)
+/// gives every `fixed_update` exactly the input it saw while recording.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, tick: u64, event: InputEvent) {
+        self.events.push(RecordedEvent { tick, event });
+    }
+
+    /// Decodes a recording produced by [`Recording::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EngineError> {
+        let Some(body) = bytes
+            .strip_prefix(MAGIC)
+            .and_then(|rest| rest.strip_prefix(&[VERSION]))
+        else {
+            return Err(EngineError::Recording(
+                "not an input recording or unsupported version".to_string(),
+            ));
+        };
+        let (recording, _) = bincode::serde::decode_from_slice(body, bincode::config::standard())
+            .map_err(|e| EngineError::Recording(e.to_string()))?;
+        Ok(recording)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        let body = bincode::serde::encode_to_vec(self, bincode::config::standard())
+            .map_err(|e| EngineError::Recording(e.to_string()))?;
+        bytes.extend(body);
+        Ok(bytes)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EngineError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EngineError> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+}
+
+/// A recording in progress. Ticks are counted from when it started.
+#[derive(Debug)]
+pub struct Recorder {
+    start: u64,
+    recording: Recording,
+}
+
+impl Recorder {
+    pub fn new(start: u64) -> Self {
+        Self {
+            start,
+            recording: Recording::new(),
+        }
+    }
+
+    pub fn push(&mut self, tick: u64, event: InputEvent) {
+        self.recording.push(tick - self.start, event);
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+/// A recording being replayed, consumed tick by tick.
+#[derive(Debug)]
+pub struct Playback {
+    start: u64,
+    events: VecDeque<RecordedEvent>,
+}
+
+impl Playback {
+    pub fn new(recording: Recording, start: u64) -> Self {
+        Self {
+            start,
+            events: recording.events.into(),
+        }
+    }
+
+    /// Removes and returns the next event recorded before or at `tick`.
+    pub fn next(&mut self, tick: u64) -> Option<InputEvent> {
+        if self.start + self.events.front()?.tick <= tick {
+            self.events.pop_front().map(|recorded| recorded.event)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
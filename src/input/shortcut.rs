@@ -0,0 +1,137 @@
+use crate::core::EngineError;
+use std::fmt;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// A key combination such as `Ctrl+Shift+S`, matched against logical keys so
+/// it follows the active keyboard layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    pub modifiers: ModifiersState,
+    pub key: Key,
+}
+
+impl Shortcut {
+    pub fn new(modifiers: ModifiersState, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// Parses `Modifier+...+Key`. Modifiers are `Ctrl`, `Shift`, `Alt` and
+    /// `Super` (or `Control`, `Option`, `Cmd`, `Meta`, `Win`); the key is a
+    /// single character or a name such as `Enter`, `Escape`, `Space`, `Tab`,
+    /// `Up` or `F5`. Matching is case-insensitive.
+    pub fn parse(shortcut: &str) -> Result<Self, EngineError> {
+        let invalid = || EngineError::Config(format!("invalid shortcut `{}`", shortcut));
+
+        let mut parts: Vec<&str> = shortcut.split('+').map(str::trim).collect();
+        // "Ctrl++" binds the plus key itself.
+        if shortcut.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let (key, modifier_names) = parts.split_last().ok_or_else(invalid)?;
+
+        let mut modifiers = ModifiersState::empty();
+        for name in modifier_names {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => ModifiersState::CONTROL,
+                "shift" => ModifiersState::SHIFT,
+                "alt" | "option" => ModifiersState::ALT,
+                "super" | "cmd" | "meta" | "win" => ModifiersState::SUPER,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let key = parse_key(key).ok_or_else(invalid)?;
+        Ok(Self { modifiers, key })
+    }
+
+    /// Whether `key` pressed with `modifiers` triggers this shortcut.
+    ///
+    /// Symbols such as `+` need Shift on some layouts, and the character
+    /// already reflects it, so Shift is ignored for a caseless character key
+    /// unless the shortcut asks for it.
+    pub fn matches(&self, modifiers: ModifiersState, key: &Key) -> bool {
+        let modifiers = if self.ignores_shift() {
+            modifiers - ModifiersState::SHIFT
+        } else {
+            modifiers
+        };
+        modifiers == self.modifiers && keys_match(&self.key, key)
+    }
+
+    fn ignores_shift(&self) -> bool {
+        match &self.key {
+            Key::Character(c) => {
+                !self.modifiers.shift_key() && c.to_lowercase() == c.to_uppercase()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (ModifiersState::CONTROL, "Ctrl"),
+            (ModifiersState::SHIFT, "Shift"),
+            (ModifiersState::ALT, "Alt"),
+            (ModifiersState::SUPER, "Super"),
+        ];
+        for (modifier, name) in names {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match &self.key {
+            Key::Character(c) => write!(f, "{}", c.to_uppercase()),
+            Key::Named(named) => write!(f, "{:?}", named),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+fn keys_match(expected: &Key, actual: &Key) -> bool {
+    match (expected, actual) {
+        (Key::Character(a), Key::Character(b)) => a.to_lowercase() == b.to_lowercase(),
+        _ => expected == actual,
+    }
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Character(c.to_lowercase().to_string().into()));
+    }
+
+    let named = match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => NamedKey::Enter,
+        "escape" | "esc" => NamedKey::Escape,
+        "space" => NamedKey::Space,
+        "tab" => NamedKey::Tab,
+        "backspace" => NamedKey::Backspace,
+        "delete" | "del" => NamedKey::Delete,
+        "insert" | "ins" => NamedKey::Insert,
+        "home" => NamedKey::Home,
+        "end" => NamedKey::End,
+        "pageup" => NamedKey::PageUp,
+        "pagedown" => NamedKey::PageDown,
+        "up" | "arrowup" => NamedKey::ArrowUp,
+        "down" | "arrowdown" => NamedKey::ArrowDown,
+        "left" | "arrowleft" => NamedKey::ArrowLeft,
+        "right" | "arrowright" => NamedKey::ArrowRight,
+        "f1" => NamedKey::F1,
+        "f2" => NamedKey::F2,
+        "f3" => NamedKey::F3,
+        "f4" => NamedKey::F4,
+        "f5" => NamedKey::F5,
+        "f6" => NamedKey::F6,
+        "f7" => NamedKey::F7,
+        "f8" => NamedKey::F8,
+        "f9" => NamedKey::F9,
+        "f10" => NamedKey::F10,
+        "f11" => NamedKey::F11,
+        "f12" => NamedKey::F12,
+        _ => return None,
+    };
+    Some(Key::Named(named))
+}
@@ -0,0 +1,201 @@
+use glam::Vec2;
+use winit::event::{ElementState, Ime, KeyEvent};
+use winit::keyboard::{Key, NamedKey};
+
+/// Editing keys delivered to text fields, including key repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCommand {
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    Submit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextEvent {
+    /// Finished text to insert at the caret.
+    Commit(String),
+    /// Text being composed by the IME. It replaces the previous preedit and is
+    /// empty when composition ends. `cursor` is a byte range into `text`.
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    Edit(EditCommand),
+}
+
+/// Window IME settings requested by game code, applied by `Application`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImeRequest {
+    pub allowed: bool,
+    /// Position and size of the text field in physical pixels, so the IME
+    /// candidate window can be placed next to it.
+    pub cursor_area: Option<(Vec2, Vec2)>,
+}
+
+/// Stream of typed text for chat boxes and name entry.
+///
+/// Nothing is recorded until [`TextInput::enable`] is called, typically when
+/// a text field gains focus. Events are per frame and cleared by
+/// `Input::end_frame`.
+#[derive(Debug, Default)]
+pub struct TextInput {
+    enabled: bool,
+    ime_active: bool,
+    preedit: String,
+    preedit_cursor: Option<(usize, usize)>,
+    cursor_area: Option<(Vec2, Vec2)>,
+    events: Vec<TextEvent>,
+    request_pending: bool,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enable(&mut self) {
+        if !self.enabled {
+            self.enabled = true;
+            self.request_pending = true;
+        }
+    }
+
+    pub fn disable(&mut self) {
+        if self.enabled {
+            self.enabled = false;
+            self.ime_active = false;
+            self.preedit.clear();
+            self.preedit_cursor = None;
+            self.request_pending = true;
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Tells the IME where the focused text field is, in physical pixels.
+    pub fn set_cursor_area(&mut self, position: Vec2, size: Vec2) {
+        if self.cursor_area != Some((position, size)) {
+            self.cursor_area = Some((position, size));
+            self.request_pending = true;
+        }
+    }
+
+    /// Text events received this frame, in order.
+    pub fn events(&self) -> &[TextEvent] {
+        &self.events
+    }
+
+    /// The current IME composition and its cursor range, if any.
+    pub fn preedit(&self) -> (&str, Option<(usize, usize)>) {
+        (&self.preedit, self.preedit_cursor)
+    }
+
+    pub fn end_frame(&mut self) {
+        self.events.clear();
+    }
+
+    /// Drops the text received so far this frame. The composition in
+    /// progress is kept.
+    pub fn consume_all(&mut self) {
+        self.events.clear();
+    }
+
+    /// Returns the IME settings to apply to the window if they changed.
+    pub fn take_ime_request(&mut self) -> Option<ImeRequest> {
+        if !self.request_pending {
+            return None;
+        }
+        self.request_pending = false;
+        Some(ImeRequest {
+            allowed: self.enabled,
+            cursor_area: self.cursor_area,
+        })
+    }
+
+    pub fn process_key_event(&mut self, event: &KeyEvent) {
+        self.process_key(&event.logical_key, event.text.as_deref(), event.state);
+    }
+
+    /// Applies a key press producing `text`, as
+    /// [`TextInput::process_key_event`] does for window events.
+    pub fn process_key(&mut self, key: &Key, text: Option<&str>, state: ElementState) {
+        if !self.enabled || state != ElementState::Pressed {
+            return;
+        }
+
+        if let Some(command) = edit_command(key) {
+            self.process_edit(command);
+        } else if let Some(text) = text
+            && !self.ime_active
+        {
+            self.process_text(text);
+        }
+    }
+
+    pub fn process_ime(&mut self, event: &Ime) {
+        if !self.enabled {
+            return;
+        }
+
+        match event {
+            Ime::Enabled => self.ime_active = true,
+            Ime::Preedit(text, cursor) => {
+                self.preedit = text.clone();
+                self.preedit_cursor = *cursor;
+                self.events.push(TextEvent::Preedit {
+                    text: text.clone(),
+                    cursor: *cursor,
+                });
+            }
+            Ime::Commit(text) => {
+                self.preedit.clear();
+                self.preedit_cursor = None;
+                self.process_text(text);
+            }
+            Ime::Disabled => {
+                self.ime_active = false;
+                self.preedit.clear();
+                self.preedit_cursor = None;
+            }
+        }
+    }
+
+    /// Records committed text, dropping control characters.
+    pub fn process_text(&mut self, text: &str) {
+        if !self.enabled {
+            return;
+        }
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if !text.is_empty() {
+            self.events.push(TextEvent::Commit(text));
+        }
+    }
+
+    pub fn process_edit(&mut self, command: EditCommand) {
+        if self.enabled {
+            self.events.push(TextEvent::Edit(command));
+        }
+    }
+}
+
+fn edit_command(key: &Key) -> Option<EditCommand> {
+    let Key::Named(named) = key else {
+        return None;
+    };
+    match named {
+        NamedKey::Backspace => Some(EditCommand::Backspace),
+        NamedKey::Delete => Some(EditCommand::Delete),
+        NamedKey::ArrowLeft => Some(EditCommand::Left),
+        NamedKey::ArrowRight => Some(EditCommand::Right),
+        NamedKey::Home => Some(EditCommand::Home),
+        NamedKey::End => Some(EditCommand::End),
+        NamedKey::Enter => Some(EditCommand::Submit),
+        _ => None,
+    }
+}
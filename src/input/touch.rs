@@ -0,0 +1,323 @@
+use glam::Vec2;
+use std::collections::BTreeMap;
+use winit::event::TouchPhase;
+
+/// A finger on a touch screen. Positions are in physical pixels relative to
+/// the top-left of the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Vec2,
+    pub start_position: Vec2,
+    /// Normalised pressure in 0..1, if the device reports it.
+    pub pressure: Option<f32>,
+    /// Time since the finger went down, in seconds.
+    pub duration: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A gesture recognised from touches or reported by a trackpad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap {
+        position: Vec2,
+    },
+    /// Sent after the [`Gesture::Tap`] that completes it.
+    DoubleTap {
+        position: Vec2,
+    },
+    LongPress {
+        position: Vec2,
+    },
+    Swipe {
+        start: Vec2,
+        end: Vec2,
+        direction: SwipeDirection,
+    },
+    /// Two fingers moving apart or together. `scale` is the change since the
+    /// previous pinch, e.g. 1.1 for 10% further apart.
+    Pinch {
+        center: Vec2,
+        scale: f32,
+    },
+    /// Two fingers moving together, by `delta` since the previous pan.
+    Pan {
+        delta: Vec2,
+    },
+}
+
+/// Thresholds used to tell gestures apart. Distances are in physical pixels,
+/// times in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureSettings {
+    pub tap_max_duration: f64,
+    pub tap_max_distance: f32,
+    pub double_tap_interval: f64,
+    pub long_press_duration: f64,
+    pub swipe_min_distance: f32,
+    pub swipe_max_duration: f64,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: 0.25,
+            tap_max_distance: 16.0,
+            double_tap_interval: 0.3,
+            long_press_duration: 0.5,
+            swipe_min_distance: 64.0,
+            swipe_max_duration: 0.5,
+        }
+    }
+}
+
+/// Distance and centre of the two fingers of a pinch or pan.
+#[derive(Debug, Clone, Copy)]
+struct TwoFingers {
+    distance: f32,
+    center: Vec2,
+}
+
+/// Tracks fingers on a touch screen and recognises gestures from them.
+///
+/// Touches and gestures are per frame; touches that ended stay visible until
+/// the end of the frame.
+///
+/// `Application` advances durations by the fixed step on every tick, so
+/// gestures are recognised the same way when a recording is replayed. They
+/// stand still while the simulation is paused.
+#[derive(Debug, Default)]
+pub struct Touches {
+    touches: BTreeMap<u64, Touch>,
+    gestures: Vec<Gesture>,
+    pub settings: GestureSettings,
+    /// Most fingers down at once since all fingers were last lifted. Only
+    /// single-finger interactions become taps, long presses or swipes.
+    max_fingers: usize,
+    long_pressed: bool,
+    two_fingers: Option<TwoFingers>,
+    last_tap: Option<(f64, Vec2)>,
+    time: f64,
+}
+
+impl Touches {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn process_touch(
+        &mut self,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2,
+        pressure: Option<f32>,
+    ) {
+        match phase {
+            TouchPhase::Started => {
+                self.touches.insert(
+                    id,
+                    Touch {
+                        id,
+                        phase,
+                        position,
+                        start_position: position,
+                        pressure,
+                        duration: 0.0,
+                    },
+                );
+                let fingers = self.count();
+                if fingers == 1 {
+                    self.max_fingers = 0;
+                    self.long_pressed = false;
+                }
+                self.max_fingers = self.max_fingers.max(fingers);
+                self.two_fingers = self.measure_two_fingers();
+            }
+            TouchPhase::Moved => {
+                if let Some(touch) = self.touches.get_mut(&id) {
+                    touch.phase = phase;
+                    touch.position = position;
+                    touch.pressure = pressure;
+                }
+                self.update_two_fingers();
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let Some(touch) = self.touches.get_mut(&id) else {
+                    return;
+                };
+                touch.phase = phase;
+                touch.position = position;
+                let touch = *touch;
+                if phase == TouchPhase::Ended && self.max_fingers == 1 {
+                    self.recognise_release(&touch);
+                }
+                self.two_fingers = self.measure_two_fingers();
+            }
+        }
+    }
+
+    /// A trackpad pinch, scaled by `scale` around `center`.
+    pub fn process_pinch(&mut self, scale: f32, center: Vec2) {
+        self.gestures.push(Gesture::Pinch { center, scale });
+    }
+
+    /// A trackpad two-finger pan.
+    pub fn process_pan(&mut self, delta: Vec2) {
+        self.gestures.push(Gesture::Pan { delta });
+    }
+
+    /// A trackpad double tap.
+    pub fn process_double_tap(&mut self, position: Vec2) {
+        self.gestures.push(Gesture::DoubleTap { position });
+    }
+
+    /// Advances the clock used for durations, long presses and double taps by
+    /// `dt` seconds.
+    pub fn update(&mut self, dt: f64) {
+        self.time += dt;
+        for touch in self.touches.values_mut() {
+            touch.duration += dt;
+        }
+
+        let touch = self.active().next().copied();
+        if self.max_fingers == 1
+            && !self.long_pressed
+            && let Some(touch) = touch
+            && touch.duration >= self.settings.long_press_duration
+            && touch.position.distance(touch.start_position) <= self.settings.tap_max_distance
+        {
+            self.long_pressed = true;
+            self.gestures.push(Gesture::LongPress {
+                position: touch.position,
+            });
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.touches
+            .retain(|_, touch| matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved));
+        for touch in self.touches.values_mut() {
+            touch.phase = TouchPhase::Moved;
+        }
+        self.gestures.clear();
+    }
+
+    /// Drops every touch without recognising gestures, e.g. on focus loss.
+    pub fn cancel_all(&mut self) {
+        for touch in self.touches.values_mut() {
+            touch.phase = TouchPhase::Cancelled;
+        }
+        self.max_fingers = 0;
+        self.two_fingers = None;
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Touch> {
+        self.touches.get(&id)
+    }
+
+    /// Touches seen this frame, including ones that ended, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.values()
+    }
+
+    /// Number of fingers currently down.
+    pub fn count(&self) -> usize {
+        self.active().count()
+    }
+
+    /// Gestures recognised this frame, in order.
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    fn active(&self) -> impl Iterator<Item = &Touch> {
+        self.touches
+            .values()
+            .filter(|touch| matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved))
+    }
+
+    fn measure_two_fingers(&self) -> Option<TwoFingers> {
+        let mut active = self.active();
+        let (a, b) = (active.next()?, active.next()?);
+        if active.next().is_some() {
+            return None;
+        }
+        Some(TwoFingers {
+            distance: a.position.distance(b.position),
+            center: (a.position + b.position) / 2.0,
+        })
+    }
+
+    fn update_two_fingers(&mut self) {
+        let (Some(previous), Some(current)) = (self.two_fingers, self.measure_two_fingers()) else {
+            return;
+        };
+        if previous.distance > 0.0 && current.distance != previous.distance {
+            self.gestures.push(Gesture::Pinch {
+                center: current.center,
+                scale: current.distance / previous.distance,
+            });
+        }
+        if current.center != previous.center {
+            self.gestures.push(Gesture::Pan {
+                delta: current.center - previous.center,
+            });
+        }
+        self.two_fingers = Some(current);
+    }
+
+    fn recognise_release(&mut self, touch: &Touch) {
+        let settings = self.settings;
+        let offset = touch.position - touch.start_position;
+        let distance = offset.length();
+
+        if self.long_pressed {
+            return;
+        }
+
+        if distance <= settings.tap_max_distance && touch.duration <= settings.tap_max_duration {
+            self.gestures.push(Gesture::Tap {
+                position: touch.position,
+            });
+            match self.last_tap {
+                Some((time, position))
+                    if self.time - time <= settings.double_tap_interval
+                        && position.distance(touch.position) <= settings.tap_max_distance =>
+                {
+                    self.gestures.push(Gesture::DoubleTap {
+                        position: touch.position,
+                    });
+                    self.last_tap = None;
+                }
+                _ => self.last_tap = Some((self.time, touch.position)),
+            }
+        } else if distance >= settings.swipe_min_distance
+            && touch.duration <= settings.swipe_max_duration
+        {
+            let direction = if offset.x.abs() >= offset.y.abs() {
+                if offset.x > 0.0 {
+                    SwipeDirection::Right
+                } else {
+                    SwipeDirection::Left
+                }
+            } else if offset.y > 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            };
+            self.gestures.push(Gesture::Swipe {
+                start: touch.start_position,
+                end: touch.position,
+                direction,
+            });
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod core;
+pub mod graphics;
+pub mod input;
+
+pub use glam;
@@ -1,12 +1,17 @@
-use crate::core::Application;
+use engine::core::{Application, EngineError, Game};
+use engine::graphics::{Color, Frame};
 
-mod core;
-mod graphics;
-mod input;
+struct Blank;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+impl Game for Blank {
+    fn render(&mut self, frame: &mut Frame, _alpha: f32) {
+        frame.clear(Color::BLACK);
+    }
+}
+
+fn main() -> Result<(), EngineError> {
     tracing_subscriber::fmt::init();
 
-    let app = Application::new("My Game Engine", 800, 600);
+    let app = Application::new("My Game Engine", 800, 600, Box::new(Blank));
     app.run()
 }
@@ -0,0 +1,164 @@
+//! Golden-image harness for tests that render through [`Frame`].
+//!
+//! Each check renders a scene off-screen and compares it against
+//! `tests/golden/<name>.png`. On mismatch the actual image and a diff are
+//! written to `target/golden/`. Run with `ENGINE_BLESS=1` to (re)write the
+//! references instead of comparing against them.
+
+use engine::core::EngineError;
+use engine::graphics::{Frame, Renderer};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+const BLESS_VAR: &str = "ENGINE_BLESS";
+
+/// Renders `draw` into a `width` x `height` frame and compares the result with
+/// the stored reference. Channels may differ by up to `tolerance` before a
+/// pixel counts as mismatched.
+///
+/// The check is skipped when the machine has no adapter at all, not even a
+/// software one.
+pub fn check_frame(
+    name: &str,
+    width: u32,
+    height: u32,
+    tolerance: u8,
+    draw: impl FnOnce(&mut Frame),
+) {
+    check_render(name, width, height, tolerance, |renderer| {
+        let mut frame = renderer.begin_frame().expect("offscreen frame");
+        draw(&mut frame);
+        frame.present();
+    });
+}
+
+/// Creates an off-screen renderer, or returns `None` and reports `test` as
+/// skipped when the machine has no adapter at all.
+pub fn headless_renderer(test: &str, width: u32, height: u32) -> Option<Renderer> {
+    match pollster::block_on(Renderer::new_headless(width, height)) {
+        Ok(renderer) => Some(renderer),
+        Err(EngineError::Adapter(e)) => {
+            eprintln!("skipping test `{}`: {}", test, e);
+            None
+        }
+        Err(e) => panic!("failed to create headless renderer: {}", e),
+    }
+}
+
+/// Like [`check_frame`], but hands over the renderer so the scene can create
+/// resources such as textures first. `render` must present a frame.
+pub fn check_render(
+    name: &str,
+    width: u32,
+    height: u32,
+    tolerance: u8,
+    render: impl FnOnce(&mut Renderer),
+) {
+    let Some(mut renderer) = headless_renderer(name, width, height) else {
+        return;
+    };
+
+    render(&mut renderer);
+
+    let actual = renderer
+        .read_pixels()
+        .expect("headless renderer")
+        .expect("pixel readback");
+
+    let reference_path = golden_dir().join(format!("{}.png", name));
+    if std::env::var_os(BLESS_VAR).is_some() {
+        write_png(&reference_path, width, height, &actual);
+        return;
+    }
+
+    let Some((ref_width, ref_height, expected)) = read_png(&reference_path) else {
+        panic!(
+            "missing reference {}; run with {}=1 to create it",
+            reference_path.display(),
+            BLESS_VAR
+        );
+    };
+    assert_eq!(
+        (ref_width, ref_height),
+        (width, height),
+        "reference `{}` has a different size",
+        name
+    );
+
+    let (mismatched, diff) = compare(&expected, &actual, tolerance);
+    if mismatched > 0 {
+        let out = output_dir();
+        let actual_path = out.join(format!("{}.actual.png", name));
+        let diff_path = out.join(format!("{}.diff.png", name));
+        write_png(&actual_path, width, height, &actual);
+        write_png(&diff_path, width, height, &diff);
+        panic!(
+            "`{}`: {} of {} pixels differ by more than {} (actual: {}, diff: {})",
+            name,
+            mismatched,
+            width * height,
+            tolerance,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+/// Counts pixels whose channels differ by more than `tolerance` and builds a
+/// diff image: mismatches in red over a dimmed copy of the expected image.
+fn compare(expected: &[u8], actual: &[u8], tolerance: u8) -> (usize, Vec<u8>) {
+    let mut mismatched = 0;
+    let mut diff = Vec::with_capacity(expected.len());
+
+    for (e, a) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        let differs = e.iter().zip(a).any(|(e, a)| e.abs_diff(*a) > tolerance);
+        if differs {
+            mismatched += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            diff.extend_from_slice(&[e[0] / 4, e[1] / 4, e[2] / 4, 255]);
+        }
+    }
+
+    (mismatched, diff)
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+}
+
+fn output_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("golden")
+}
+
+fn read_png(path: &Path) -> Option<(u32, u32, Vec<u8>)> {
+    let file = File::open(path).ok()?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::ALPHA);
+    let mut reader = decoder.read_info().expect("valid png");
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).expect("valid png frame");
+    assert_eq!(
+        info.color_type,
+        png::ColorType::Rgba,
+        "reference {} must be RGBA",
+        path.display()
+    );
+    buf.truncate(info.buffer_size());
+    Some((info.width, info.height, buf))
+}
+
+fn write_png(path: &Path, width: u32, height: u32, pixels: &[u8]) {
+    std::fs::create_dir_all(path.parent().unwrap()).expect("create output directory");
+    let file = File::create(path).expect("create png");
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().expect("png header");
+    writer.write_image_data(pixels).expect("png data");
+}
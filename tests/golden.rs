@@ -0,0 +1,124 @@
+mod common;
+
+use common::{check_frame, check_render};
+use engine::glam::Vec2;
+use engine::graphics::{Color, Rect, Transform2D};
+
+const SIZE: u32 = 64;
+const TOLERANCE: u8 = 2;
+
+#[test]
+fn circle() {
+    check_frame("circle", SIZE, SIZE, TOLERANCE, |frame| {
+        frame.clear(Color::BLACK);
+        frame.draw_circle(16.0, 32, Color::GREEN);
+    });
+}
+
+#[test]
+fn triangle() {
+    check_frame("triangle", SIZE, SIZE, TOLERANCE, |frame| {
+        frame.clear(Color::BLACK);
+        frame.draw_triangle(48.0, Color::RED);
+    });
+}
+
+#[test]
+fn rectangle() {
+    check_frame("rectangle", SIZE, SIZE, TOLERANCE, |frame| {
+        frame.clear(Color::BLACK);
+        frame.draw_rectangle(48.0, 16.0, Color::BLUE);
+    });
+}
+
+#[test]
+fn alpha_blending() {
+    check_frame("alpha_blending", SIZE, SIZE, TOLERANCE, |frame| {
+        frame.clear(Color::WHITE);
+        frame.draw_quad(32.0, Color::RED);
+        frame.draw_circle(
+            16.0,
+            32,
+            Color {
+                a: 0.5,
+                ..Color::BLUE
+            },
+        );
+    });
+}
+
+#[test]
+fn camera_non_square_viewport() {
+    check_frame("camera_non_square_viewport", 96, 48, TOLERANCE, |frame| {
+        let camera = frame.camera_mut();
+        camera.position = Vec2::new(8.0, 0.0);
+        camera.zoom = 2.0;
+
+        frame.clear(Color::BLACK);
+        frame.draw_circle(8.0, 32, Color::WHITE);
+    });
+}
+
+#[test]
+fn transforms() {
+    check_frame("transforms", SIZE, SIZE, TOLERANCE, |frame| {
+        frame.clear(Color::BLACK);
+        frame.draw_rectangle_at(
+            24.0,
+            8.0,
+            Color::RED,
+            &Transform2D::from_position(Vec2::new(-16.0, 16.0))
+                .with_rotation(std::f32::consts::FRAC_PI_4),
+        );
+        frame.draw_quad_at(
+            8.0,
+            Color::GREEN,
+            &Transform2D::from_position(Vec2::new(8.0, -8.0))
+                .with_scale(Vec2::new(2.0, 1.0))
+                .with_anchor(Vec2::new(-4.0, -4.0)),
+        );
+        frame.draw_circle_at(
+            6.0,
+            16,
+            Color::BLUE,
+            &Transform2D::from_position(Vec2::new(-16.0, -16.0)),
+        );
+    });
+}
+
+#[test]
+fn sprites() {
+    check_render("sprites", SIZE, SIZE, TOLERANCE, |renderer| {
+        #[rustfmt::skip]
+        let checker = [
+            255, 255, 255, 255,   0,   0,   0, 255,
+              0,   0,   0, 255, 255, 255, 255, 255,
+        ];
+        let texture = renderer.create_texture(2, 2, &checker).expect("texture");
+
+        let mut frame = renderer.begin_frame().expect("offscreen frame");
+        frame.clear(Color::BLUE);
+        frame.draw_sprite(
+            &texture,
+            Rect::new(-32.0, 0.0, 32.0, 32.0),
+            None,
+            Color::WHITE,
+        );
+        frame.draw_sprite(
+            &texture,
+            Rect::new(0.0, 0.0, 32.0, 32.0),
+            Some(Rect::new(1.0, 0.0, 1.0, 1.0)),
+            Color::WHITE,
+        );
+        frame.draw_rectangle(16.0, 16.0, Color::GREEN);
+        frame.draw_sprite(
+            &texture,
+            Rect::new(-32.0, -32.0, 64.0, 16.0),
+            None,
+            Color::RED,
+        );
+        frame.present();
+
+        assert_eq!(renderer.stats().batches, 3);
+    });
+}
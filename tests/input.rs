@@ -0,0 +1,854 @@
+use engine::core::EngineError;
+use engine::glam::Vec2;
+use engine::input::{
+    ActionMap, Axis, Axis2D, AxisSettings, Binding, Combo, ComboStep, CursorMode, CursorRequest,
+    EditCommand, GamepadAxis, GamepadButton, GamepadEvent, Gesture, ImeRequest, Input,
+    InputContext, InputEvent, Recording, ResponseCurve, Shortcut, SwipeDirection, TextEvent,
+    VirtualGamepads,
+};
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta, TouchPhase};
+use winit::keyboard::{Key, KeyCode, ModifiersState, NamedKey, PhysicalKey};
+
+/// Runs one frame the way `Application` does: `ticks` fixed steps, then the
+/// per-frame update and render. Returns whether each step and the frame saw
+/// `key` as just pressed.
+fn run_frame(input: &mut Input, ticks: usize, key: KeyCode) -> (Vec<bool>, bool) {
+    let mut seen_by_ticks = Vec::new();
+    for _ in 0..ticks {
+        input.begin_tick();
+        seen_by_ticks.push(input.keyboard.is_just_pressed(&key));
+        input.end_tick();
+    }
+
+    let seen_by_update = input.keyboard.is_just_pressed(&key);
+    let seen_by_render = input.keyboard.is_just_pressed(&key);
+    assert_eq!(seen_by_update, seen_by_render);
+    input.end_frame();
+
+    (seen_by_ticks, seen_by_update)
+}
+
+#[test]
+fn press_is_seen_by_one_tick_when_several_run_in_a_frame() {
+    let mut input = Input::new();
+    input
+        .keyboard
+        .process_key(KeyCode::Space, ElementState::Pressed);
+
+    let (ticks, frame) = run_frame(&mut input, 3, KeyCode::Space);
+    assert_eq!(ticks, [true, false, false]);
+    assert!(frame);
+
+    let (ticks, frame) = run_frame(&mut input, 1, KeyCode::Space);
+    assert_eq!(ticks, [false]);
+    assert!(!frame);
+    assert!(input.keyboard.is_pressed(&KeyCode::Space));
+}
+
+#[test]
+fn press_survives_frames_without_a_tick() {
+    let mut input = Input::new();
+    input
+        .keyboard
+        .process_key(KeyCode::Space, ElementState::Pressed);
+
+    let (ticks, frame) = run_frame(&mut input, 0, KeyCode::Space);
+    assert!(ticks.is_empty());
+    assert!(frame);
+
+    let (ticks, frame) = run_frame(&mut input, 0, KeyCode::Space);
+    assert!(ticks.is_empty());
+    assert!(!frame);
+
+    let (ticks, frame) = run_frame(&mut input, 2, KeyCode::Space);
+    assert_eq!(ticks, [true, false]);
+    assert!(!frame);
+}
+
+#[test]
+fn tap_within_one_frame_reports_both_edges() {
+    let mut input = Input::new();
+    input
+        .keyboard
+        .process_key(KeyCode::KeyE, ElementState::Pressed);
+    input
+        .keyboard
+        .process_key(KeyCode::KeyE, ElementState::Released);
+
+    input.begin_tick();
+    assert!(input.keyboard.is_just_pressed(&KeyCode::KeyE));
+    assert!(input.keyboard.is_just_released(&KeyCode::KeyE));
+    assert!(!input.keyboard.is_pressed(&KeyCode::KeyE));
+    input.end_tick();
+
+    assert!(input.keyboard.is_just_pressed(&KeyCode::KeyE));
+    assert!(input.keyboard.is_just_released(&KeyCode::KeyE));
+    input.end_frame();
+
+    assert!(!input.keyboard.is_just_pressed(&KeyCode::KeyE));
+    assert!(!input.keyboard.is_just_released(&KeyCode::KeyE));
+}
+
+#[test]
+fn key_repeat_does_not_retrigger_just_pressed() {
+    let mut input = Input::new();
+    input
+        .keyboard
+        .process_key(KeyCode::KeyW, ElementState::Pressed);
+    input.end_frame();
+
+    input
+        .keyboard
+        .process_key(KeyCode::KeyW, ElementState::Pressed);
+    assert!(input.keyboard.is_pressed(&KeyCode::KeyW));
+    assert!(!input.keyboard.is_just_pressed(&KeyCode::KeyW));
+}
+
+#[test]
+fn mouse_buttons_follow_the_same_phases() {
+    let mut input = Input::new();
+    input
+        .mouse
+        .process_button(MouseButton::Left, ElementState::Pressed);
+    input.end_frame();
+
+    input.begin_tick();
+    assert!(input.mouse.is_just_pressed(&MouseButton::Left));
+    input.end_tick();
+
+    input.begin_tick();
+    assert!(!input.mouse.is_just_pressed(&MouseButton::Left));
+    input.end_tick();
+}
+
+#[test]
+fn scroll_accumulates_lines_and_pixels_separately() {
+    let mut input = Input::new();
+    input.handle(InputEvent::MouseWheel(MouseScrollDelta::LineDelta(
+        0.0, 1.0,
+    )));
+    input.handle(InputEvent::MouseWheel(MouseScrollDelta::LineDelta(
+        0.5, 2.0,
+    )));
+    input.handle(InputEvent::MouseWheel(MouseScrollDelta::PixelDelta(
+        PhysicalPosition::new(4.0, -12.0),
+    )));
+
+    assert_eq!(input.mouse.scroll_lines(), Vec2::new(0.5, 3.0));
+    assert_eq!(input.mouse.scroll_pixels(), Vec2::new(4.0, -12.0));
+
+    input.end_frame();
+    assert_eq!(input.mouse.scroll_lines(), Vec2::ZERO);
+    assert_eq!(input.mouse.scroll_pixels(), Vec2::ZERO);
+}
+
+#[test]
+fn logical_cursor_position_follows_the_scale_factor() {
+    let mut input = Input::new();
+    input.mouse.set_scale_factor(2.0);
+    input.handle(InputEvent::CursorMoved(PhysicalPosition::new(300.0, 150.0)));
+
+    assert_eq!(input.mouse.position(), PhysicalPosition::new(300.0, 150.0));
+    let logical = input.mouse.logical_position();
+    assert_eq!((logical.x, logical.y), (150.0, 75.0));
+}
+
+#[test]
+fn cursor_enter_and_leave_track_whether_it_is_inside() {
+    let mut input = Input::new();
+    assert!(!input.mouse.is_inside());
+
+    input.handle(InputEvent::CursorEntered);
+    assert!(input.mouse.is_inside());
+
+    input.handle(InputEvent::CursorLeft);
+    assert!(!input.mouse.is_inside());
+}
+
+fn shortcut(source: &str) -> Shortcut {
+    Shortcut::parse(source).unwrap()
+}
+
+#[test]
+fn shortcut_follows_the_logical_layout() {
+    let mut input = Input::new();
+    input.keyboard.set_modifiers(ModifiersState::CONTROL);
+    // On AZERTY the key in the QWERTY "W" position types "z".
+    input.keyboard.process_logical_key(
+        PhysicalKey::Code(KeyCode::KeyW),
+        Key::Character("z".into()),
+        ElementState::Pressed,
+    );
+
+    assert!(input.shortcut(&shortcut("Ctrl+Z")));
+    assert!(!input.shortcut(&shortcut("Ctrl+W")));
+    assert!(!input.shortcut(&shortcut("Ctrl+Shift+Z")));
+    assert!(!input.shortcut(&shortcut("Z")));
+}
+
+#[test]
+fn logical_release_matches_the_press_after_modifiers_change() {
+    let mut input = Input::new();
+    let physical = PhysicalKey::Code(KeyCode::KeyS);
+    input
+        .keyboard
+        .set_modifiers(ModifiersState::CONTROL | ModifiersState::SHIFT);
+    input
+        .keyboard
+        .process_logical_key(physical, Key::Character("S".into()), ElementState::Pressed);
+    assert!(input.shortcut(&shortcut("Ctrl+Shift+S")));
+    input.end_frame();
+
+    input.keyboard.set_modifiers(ModifiersState::empty());
+    input.keyboard.process_logical_key(
+        physical,
+        Key::Character("s".into()),
+        ElementState::Released,
+    );
+    assert!(
+        !input
+            .keyboard
+            .is_logical_pressed(&Key::Character("S".into()))
+    );
+    assert!(
+        input
+            .keyboard
+            .is_logical_just_released(&Key::Character("S".into()))
+    );
+}
+
+#[test]
+fn symbol_shortcut_ignores_the_shift_that_types_it() {
+    let mut input = Input::new();
+    input
+        .keyboard
+        .set_modifiers(ModifiersState::CONTROL | ModifiersState::SHIFT);
+    // On US layouts "+" is Shift+"=".
+    input.keyboard.process_logical_key(
+        PhysicalKey::Code(KeyCode::Equal),
+        Key::Character("+".into()),
+        ElementState::Pressed,
+    );
+
+    assert!(input.shortcut(&shortcut("Ctrl++")));
+    assert!(!input.shortcut(&shortcut("Ctrl+Alt++")));
+}
+
+#[test]
+fn shortcut_parsing() {
+    let save = Shortcut::parse("ctrl+shift+s").unwrap();
+    assert_eq!(
+        save.modifiers,
+        ModifiersState::CONTROL | ModifiersState::SHIFT
+    );
+    assert_eq!(save.key, Key::Character("s".into()));
+    assert_eq!(save.to_string(), "Ctrl+Shift+S");
+
+    let zoom_in = Shortcut::parse("Ctrl++").unwrap();
+    assert_eq!(zoom_in.key, Key::Character("+".into()));
+
+    assert_eq!(
+        Shortcut::parse("Alt+F4").unwrap().key,
+        Key::Named(NamedKey::F4)
+    );
+    assert!(Shortcut::parse("Hyper+S").is_err());
+    assert!(Shortcut::parse("Ctrl+Nope").is_err());
+}
+
+#[test]
+fn action_map_round_trips_through_a_file() {
+    let mut actions = ActionMap::new();
+    actions.bind("jump", Binding::Key(KeyCode::Space));
+    actions.bind("jump", Binding::Gamepad(GamepadButton::South));
+    actions.bind("fire", Binding::Mouse(MouseButton::Left));
+    actions.bind("fire", Binding::Mouse(MouseButton::Other(4)));
+    actions.bind_axis("steer", Axis::keys(KeyCode::KeyA, KeyCode::KeyD));
+    actions.bind_axis_2d("look", Axis2D::mouse());
+
+    let path = std::env::temp_dir().join(format!("engine-bindings-{}.toml", std::process::id()));
+    actions.save(&path).unwrap();
+    let loaded = ActionMap::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), actions);
+}
+
+#[test]
+fn rebinding_takes_effect_immediately() {
+    let mut input = Input::new();
+    input.actions.bind("jump", Binding::Key(KeyCode::Space));
+    input
+        .actions
+        .set_bindings("jump", vec![Binding::Key(KeyCode::KeyJ)]);
+
+    input
+        .keyboard
+        .process_key(KeyCode::Space, ElementState::Pressed);
+    assert!(!input.action_just_pressed("jump"));
+    input
+        .keyboard
+        .process_key(KeyCode::KeyJ, ElementState::Pressed);
+    assert!(input.action_just_pressed("jump"));
+
+    input.actions.unbind("jump", Binding::Key(KeyCode::KeyJ));
+    assert!(!input.action_pressed("jump"));
+}
+
+#[test]
+fn malformed_action_map_is_a_config_error() {
+    for source in ["[actions]\njump = [{ key = \"NoSuchKey\" }]", "[actions\n"] {
+        assert!(matches!(
+            ActionMap::from_toml(source),
+            Err(EngineError::Config(_))
+        ));
+    }
+
+    let path = std::env::temp_dir().join(format!("engine-malformed-{}.toml", std::process::id()));
+    std::fs::write(&path, "actions = 3").unwrap();
+    let loaded = ActionMap::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(loaded, Err(EngineError::Config(_))));
+}
+
+#[test]
+fn text_is_only_recorded_while_enabled() {
+    let mut input = Input::new();
+    input.text.process_text("ignored");
+    assert!(input.text.events().is_empty());
+
+    input.text.enable();
+    assert_eq!(
+        input.text.take_ime_request(),
+        Some(ImeRequest {
+            allowed: true,
+            cursor_area: None
+        })
+    );
+    assert_eq!(input.text.take_ime_request(), None);
+
+    input.text.process_text("hi\r");
+    input.text.process_edit(EditCommand::Backspace);
+    assert_eq!(
+        input.text.events(),
+        [
+            TextEvent::Commit("hi".into()),
+            TextEvent::Edit(EditCommand::Backspace)
+        ]
+    );
+
+    input.end_frame();
+    assert!(input.text.events().is_empty());
+}
+
+#[test]
+fn ime_composition_ends_in_a_commit() {
+    let mut input = Input::new();
+    input.text.enable();
+
+    input.text.process_ime(&Ime::Enabled);
+    input
+        .text
+        .process_ime(&Ime::Preedit("にほ".into(), Some((6, 6))));
+    assert_eq!(input.text.preedit(), ("にほ", Some((6, 6))));
+
+    input.text.process_ime(&Ime::Commit("日本".into()));
+    assert_eq!(input.text.preedit(), ("", None));
+    assert_eq!(
+        input.text.events().last(),
+        Some(&TextEvent::Commit("日本".into()))
+    );
+}
+
+#[test]
+fn virtual_gamepad_hotplug_and_buttons() {
+    let mut input = Input::new();
+    let backend = VirtualGamepads::new();
+    input.gamepads.set_backend(backend.clone());
+    input
+        .actions
+        .bind("jump", Binding::Gamepad(GamepadButton::South));
+
+    let pad = backend.connect("Virtual Pad");
+    pad.press(GamepadButton::South);
+    assert!(input.poll_gamepads());
+    assert!(!input.poll_gamepads());
+    assert_eq!(input.gamepads.iter().count(), 1);
+    assert_eq!(input.gamepads.get(pad.id()).unwrap().name(), "Virtual Pad");
+    assert!(input.action_just_pressed("jump"));
+
+    input.end_frame();
+    assert!(input.action_pressed("jump"));
+    assert!(!input.action_just_pressed("jump"));
+
+    let id = pad.id();
+    pad.disconnect();
+    input.poll_gamepads();
+    assert!(input.gamepads.get(id).is_none());
+    assert_eq!(input.gamepads.events(), [GamepadEvent::Disconnected { id }]);
+    assert!(!input.action_pressed("jump"));
+}
+
+#[test]
+fn gamepad_stick_applies_dead_zone() {
+    let mut input = Input::new();
+    let backend = VirtualGamepads::new();
+    input.gamepads.set_backend(backend.clone());
+    input.actions.bind_axis_2d(
+        "move",
+        Axis2D::left_stick().with_settings(AxisSettings {
+            dead_zone: 0.2,
+            ..AxisSettings::default()
+        }),
+    );
+
+    let pad = backend.connect("Virtual Pad");
+    pad.set_axis(GamepadAxis::LeftStickX, 0.1);
+    input.poll_gamepads();
+    assert_eq!(input.axis_2d("move"), Vec2::ZERO);
+
+    pad.set_axis(GamepadAxis::LeftStickX, 1.0);
+    input.poll_gamepads();
+    assert!((input.axis_2d("move") - Vec2::X).length() < 1e-5);
+}
+
+#[test]
+fn axis_settings_shape_dead_zone_curve_and_sensitivity() {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+
+    let dead_zone = AxisSettings {
+        dead_zone: 0.5,
+        ..AxisSettings::default()
+    };
+    assert_eq!(dead_zone.apply(0.4), 0.0);
+    assert!(close(dead_zone.apply(0.75), 0.5));
+    assert!(close(dead_zone.apply(-1.0), -1.0));
+
+    let curve = AxisSettings {
+        curve: ResponseCurve::Power(2.0),
+        ..AxisSettings::default()
+    };
+    assert!(close(curve.apply(0.5), 0.25));
+    assert!(close(curve.apply(-0.5), -0.25));
+
+    let sensitive = AxisSettings {
+        sensitivity: 2.0,
+        ..AxisSettings::default()
+    };
+    assert!(close(sensitive.apply(0.25), 0.5));
+    assert_eq!(sensitive.apply(0.8), 1.0);
+    assert_eq!(sensitive.apply(-0.8), -1.0);
+    assert!(close(sensitive.apply_2d(Vec2::new(0.6, 0.6)).length(), 1.0));
+
+    // A dead zone of 1 would divide by zero.
+    let full = AxisSettings {
+        dead_zone: 1.0,
+        ..AxisSettings::default()
+    };
+    assert_eq!(full.apply(0.5), 0.0);
+    assert_eq!(full.apply(1.0), 1.0);
+    assert!(full.apply_2d(Vec2::X).is_finite());
+}
+
+#[test]
+fn recording_replays_on_the_same_ticks() {
+    let key = |state| InputEvent::Key {
+        physical: PhysicalKey::Code(KeyCode::Space),
+        logical: Key::Named(NamedKey::Space),
+        text: Some(" ".into()),
+        state,
+    };
+    let script = [
+        (1, key(ElementState::Pressed)),
+        (3, key(ElementState::Released)),
+        (4, InputEvent::MouseMotion { dx: 2.0, dy: -1.0 }),
+    ];
+
+    let mut live = Input::new();
+    live.start_recording();
+    let mut seen_live = Vec::new();
+    for tick in 0..6 {
+        for (_, event) in script.iter().filter(|(t, _)| *t == tick) {
+            live.handle(event.clone());
+        }
+        live.begin_tick();
+        seen_live.push((
+            live.keyboard.is_pressed(&KeyCode::Space),
+            live.mouse.delta(),
+        ));
+        live.end_tick();
+    }
+    let recording = live.stop_recording().unwrap();
+    let recording = Recording::from_bytes(&recording.to_bytes().unwrap()).unwrap();
+
+    let mut replay = Input::new();
+    replay.start_playback(recording);
+    let mut seen_replay = Vec::new();
+    for _ in 0..6 {
+        // Live events are ignored until the recording runs out.
+        if replay.is_playing_back() {
+            replay.handle(InputEvent::MouseMotion { dx: 5.0, dy: 5.0 });
+        }
+        replay.begin_tick();
+        seen_replay.push((
+            replay.keyboard.is_pressed(&KeyCode::Space),
+            replay.mouse.delta(),
+        ));
+        replay.end_tick();
+    }
+    assert_eq!(seen_live, seen_replay);
+    assert!(!replay.is_playing_back());
+}
+
+#[test]
+fn recording_rejects_foreign_bytes() {
+    assert!(Recording::from_bytes(b"not a recording").is_err());
+}
+
+#[test]
+fn consumed_key_is_hidden_until_released() {
+    let mut input = Input::new();
+    input.actions.bind("confirm", Binding::Key(KeyCode::Enter));
+    input
+        .keyboard
+        .process_key(KeyCode::Enter, ElementState::Pressed);
+
+    // The UI layer handles the press before gameplay looks at it.
+    assert!(input.action_just_pressed("confirm"));
+    input.consume_action("confirm");
+    assert!(!input.keyboard.is_pressed(&KeyCode::Enter));
+    assert!(!input.action_just_pressed("confirm"));
+
+    input
+        .keyboard
+        .process_key(KeyCode::Enter, ElementState::Released);
+    assert!(!input.keyboard.is_just_released(&KeyCode::Enter));
+
+    input
+        .keyboard
+        .process_key(KeyCode::Enter, ElementState::Pressed);
+    assert!(input.keyboard.is_just_pressed(&KeyCode::Enter));
+}
+
+#[test]
+fn blocking_context_deactivates_lower_layers() {
+    let mut input = Input::new();
+    input.push_context(InputContext::new("gameplay"));
+    input.push_context(InputContext::new("hud"));
+    assert!(input.is_context_active("gameplay"));
+
+    input
+        .keyboard
+        .process_key(KeyCode::KeyW, ElementState::Pressed);
+    input.push_context(InputContext::new("pause_menu").blocking());
+    assert!(!input.is_context_active("gameplay"));
+    assert!(!input.is_context_active("hud"));
+    assert!(input.is_context_active("pause_menu"));
+    assert!(!input.keyboard.is_pressed(&KeyCode::KeyW));
+
+    assert_eq!(input.pop_context().unwrap().name(), "pause_menu");
+    assert!(input.is_context_active("gameplay"));
+    assert!(!input.is_context_active("console"));
+}
+
+#[test]
+fn inactive_context_sees_nothing_and_menu_presses_do_not_fall_through() {
+    let mut input = Input::new();
+    input.actions.bind("jump", Binding::Key(KeyCode::Space));
+    input.push_context(InputContext::new("gameplay"));
+    input.push_context(InputContext::new("pause_menu").blocking());
+
+    input
+        .keyboard
+        .process_key(KeyCode::Space, ElementState::Pressed);
+    assert!(input.context("pause_menu").action_just_pressed("jump"));
+    assert!(!input.context("gameplay").action_just_pressed("jump"));
+    assert!(!input.context("gameplay").key_pressed(&KeyCode::Space));
+
+    // The menu closes while Space is still held.
+    input.pop_context();
+    assert!(input.context("gameplay").is_active());
+    assert!(!input.context("gameplay").action_pressed("jump"));
+    input
+        .keyboard
+        .process_key(KeyCode::Space, ElementState::Released);
+    input
+        .keyboard
+        .process_key(KeyCode::Space, ElementState::Pressed);
+    assert!(input.context("gameplay").action_just_pressed("jump"));
+}
+
+#[test]
+fn consume_all_hides_axes_motion_and_text() {
+    let mut input = Input::new();
+    let gamepads = VirtualGamepads::new();
+    input.gamepads.set_backend(gamepads.clone());
+    let pad = gamepads.connect("Pad");
+    pad.set_axis(GamepadAxis::LeftStickX, 0.8);
+    input.poll_gamepads();
+    input.mouse.process_motion(4.0, 2.0);
+    input.text.enable();
+    input.text.process_text("w");
+
+    input.consume_all();
+    assert_eq!(input.gamepads.axis(GamepadAxis::LeftStickX), 0.0);
+    assert_eq!(input.mouse.delta(), Vec2::ZERO);
+    assert!(input.text.events().is_empty());
+
+    // The stick stays hidden until it comes back to rest.
+    pad.set_axis(GamepadAxis::LeftStickX, 0.9);
+    input.poll_gamepads();
+    assert_eq!(input.gamepads.axis(GamepadAxis::LeftStickX), 0.0);
+    pad.set_axis(GamepadAxis::LeftStickX, 0.0);
+    pad.set_axis(GamepadAxis::LeftStickX, 0.5);
+    input.poll_gamepads();
+    assert_eq!(input.gamepads.axis(GamepadAxis::LeftStickX), 0.5);
+}
+
+#[test]
+fn focus_loss_releases_held_input() {
+    let mut input = Input::new();
+    input
+        .keyboard
+        .process_key(KeyCode::KeyW, ElementState::Pressed);
+    input
+        .mouse
+        .process_button(MouseButton::Left, ElementState::Pressed);
+    input.end_frame();
+    assert!(input.is_focused());
+
+    input.handle(InputEvent::Focused(false));
+    assert!(!input.is_focused());
+    assert_eq!(input.focus_changed(), Some(false));
+    assert!(!input.keyboard.is_pressed(&KeyCode::KeyW));
+    assert!(input.keyboard.is_just_released(&KeyCode::KeyW));
+    assert!(!input.mouse.is_pressed(&MouseButton::Left));
+
+    input.end_frame();
+    assert_eq!(input.focus_changed(), None);
+    input.handle(InputEvent::Focused(true));
+    assert_eq!(input.focus_changed(), Some(true));
+}
+
+/// Runs one fixed tick with `keys` held, pressing and releasing as needed,
+/// and returns the combos triggered on it.
+fn combo_tick(input: &mut Input, keys: &[KeyCode]) -> Vec<String> {
+    for key in [KeyCode::KeyS, KeyCode::KeyD, KeyCode::KeyA, KeyCode::KeyJ] {
+        let state = if keys.contains(&key) {
+            ElementState::Pressed
+        } else {
+            ElementState::Released
+        };
+        input.keyboard.process_key(key, state);
+    }
+    input.begin_tick();
+    let triggered = input.combos.triggered().to_vec();
+    input.end_tick();
+    triggered
+}
+
+fn combo_input() -> Input {
+    let mut input = Input::new();
+    input.actions.bind("down", Binding::Key(KeyCode::KeyS));
+    input.actions.bind("forward", Binding::Key(KeyCode::KeyD));
+    input.actions.bind("back", Binding::Key(KeyCode::KeyA));
+    input.actions.bind("punch", Binding::Key(KeyCode::KeyJ));
+    input
+}
+
+#[test]
+fn combo_matches_within_leniency() {
+    let mut input = combo_input();
+    input.combos.register(
+        "fireball",
+        Combo::new(vec![
+            ComboStep::press("down"),
+            ComboStep::chord(["down", "forward"]),
+            ComboStep::chord(["forward", "punch"]),
+        ])
+        .with_leniency(3),
+    );
+
+    use KeyCode::{KeyD, KeyJ, KeyS};
+    assert!(combo_tick(&mut input, &[KeyS]).is_empty());
+    assert!(combo_tick(&mut input, &[KeyS, KeyD]).is_empty());
+    assert!(combo_tick(&mut input, &[KeyD]).is_empty());
+    assert_eq!(combo_tick(&mut input, &[KeyD, KeyJ]), ["fireball"]);
+    // Holding the last step does not trigger again.
+    assert!(combo_tick(&mut input, &[KeyD, KeyJ]).is_empty());
+
+    // Too slow between steps.
+    combo_tick(&mut input, &[]);
+    combo_tick(&mut input, &[KeyS]);
+    combo_tick(&mut input, &[KeyS, KeyD]);
+    for _ in 0..4 {
+        combo_tick(&mut input, &[KeyD]);
+    }
+    assert!(combo_tick(&mut input, &[KeyD, KeyJ]).is_empty());
+}
+
+#[test]
+fn combo_uses_an_earlier_step_when_the_latest_is_too_late() {
+    let mut input = combo_input();
+    input.combos.register(
+        "dash",
+        Combo::new(vec![
+            ComboStep::press("back"),
+            ComboStep::press("forward"),
+            ComboStep::press("punch"),
+        ])
+        .with_leniency(3),
+    );
+
+    use KeyCode::{KeyA, KeyD, KeyJ};
+    combo_tick(&mut input, &[KeyA]);
+    combo_tick(&mut input, &[]);
+    combo_tick(&mut input, &[]);
+    // Forward three ticks after back, then again five ticks after it.
+    combo_tick(&mut input, &[KeyD]);
+    combo_tick(&mut input, &[]);
+    combo_tick(&mut input, &[KeyD]);
+    assert_eq!(combo_tick(&mut input, &[KeyJ]), ["dash"]);
+}
+
+#[test]
+fn charge_combo_needs_a_long_enough_hold() {
+    let mut input = combo_input();
+    input.combos.register(
+        "sonic_boom",
+        Combo::new(vec![
+            ComboStep::charge("back", 5),
+            ComboStep::chord(["forward", "punch"]),
+        ]),
+    );
+
+    use KeyCode::{KeyA, KeyD, KeyJ};
+    for _ in 0..3 {
+        combo_tick(&mut input, &[KeyA]);
+    }
+    assert!(combo_tick(&mut input, &[KeyD, KeyJ]).is_empty());
+
+    combo_tick(&mut input, &[]);
+    for _ in 0..5 {
+        combo_tick(&mut input, &[KeyA]);
+    }
+    assert_eq!(combo_tick(&mut input, &[KeyD, KeyJ]), ["sonic_boom"]);
+}
+
+fn touch(input: &mut Input, id: u64, phase: TouchPhase, x: f64, y: f64) {
+    input.handle(InputEvent::Touch {
+        id,
+        phase,
+        position: PhysicalPosition::new(x, y),
+        pressure: None,
+    });
+}
+
+#[test]
+fn taps_double_taps_and_long_presses() {
+    let mut input = Input::new();
+    touch(&mut input, 0, TouchPhase::Started, 100.0, 100.0);
+    assert_eq!(input.touches.count(), 1);
+    input.touches.update(0.1);
+    touch(&mut input, 0, TouchPhase::Ended, 102.0, 101.0);
+    assert_eq!(
+        input.touches.gestures(),
+        [Gesture::Tap {
+            position: Vec2::new(102.0, 101.0)
+        }]
+    );
+    input.end_frame();
+    assert_eq!(input.touches.iter().count(), 0);
+
+    input.touches.update(0.1);
+    touch(&mut input, 1, TouchPhase::Started, 100.0, 100.0);
+    touch(&mut input, 1, TouchPhase::Ended, 100.0, 100.0);
+    assert!(matches!(
+        input.touches.gestures(),
+        [Gesture::Tap { .. }, Gesture::DoubleTap { .. }]
+    ));
+    input.end_frame();
+
+    touch(&mut input, 2, TouchPhase::Started, 50.0, 50.0);
+    input.touches.update(0.3);
+    assert!(input.touches.gestures().is_empty());
+    input.touches.update(0.3);
+    assert!(matches!(
+        input.touches.gestures(),
+        [Gesture::LongPress { .. }]
+    ));
+    input.end_frame();
+    touch(&mut input, 2, TouchPhase::Ended, 50.0, 50.0);
+    assert!(input.touches.gestures().is_empty());
+}
+
+#[test]
+fn swipe_pinch_and_pan() {
+    let mut input = Input::new();
+    touch(&mut input, 0, TouchPhase::Started, 0.0, 100.0);
+    input.touches.update(0.1);
+    touch(&mut input, 0, TouchPhase::Moved, 120.0, 110.0);
+    touch(&mut input, 0, TouchPhase::Ended, 200.0, 110.0);
+    assert!(matches!(
+        input.touches.gestures(),
+        [Gesture::Swipe {
+            direction: SwipeDirection::Right,
+            ..
+        }]
+    ));
+    input.end_frame();
+
+    touch(&mut input, 1, TouchPhase::Started, 100.0, 100.0);
+    touch(&mut input, 2, TouchPhase::Started, 200.0, 100.0);
+    touch(&mut input, 2, TouchPhase::Moved, 300.0, 100.0);
+    assert_eq!(
+        input.touches.gestures(),
+        [
+            Gesture::Pinch {
+                center: Vec2::new(200.0, 100.0),
+                scale: 2.0
+            },
+            Gesture::Pan {
+                delta: Vec2::new(50.0, 0.0)
+            }
+        ]
+    );
+    touch(&mut input, 1, TouchPhase::Ended, 100.0, 100.0);
+    touch(&mut input, 2, TouchPhase::Ended, 300.0, 100.0);
+    // Lifting the fingers of a pinch is not a tap.
+    assert_eq!(input.touches.gestures().len(), 2);
+}
+
+#[test]
+fn grabbed_cursor_is_released_on_escape_and_focus_loss() {
+    let mut input = Input::new();
+    input.mouse.grab_cursor();
+    assert!(input.mouse.is_relative());
+    assert_eq!(
+        input.mouse.take_cursor_request(),
+        Some(CursorRequest {
+            mode: CursorMode::Locked,
+            visible: false
+        })
+    );
+    assert_eq!(input.mouse.take_cursor_request(), None);
+
+    // The cursor stays put while locked; only raw motion is reported.
+    input.handle(InputEvent::CursorMoved(PhysicalPosition::new(10.0, 10.0)));
+    input.handle(InputEvent::MouseMotion { dx: 3.0, dy: -2.0 });
+    assert_eq!(input.mouse.position(), PhysicalPosition::new(0.0, 0.0));
+    assert_eq!(input.mouse.delta(), Vec2::new(3.0, -2.0));
+
+    input.handle(InputEvent::Key {
+        physical: PhysicalKey::Code(KeyCode::Escape),
+        logical: Key::Named(NamedKey::Escape),
+        text: None,
+        state: ElementState::Pressed,
+    });
+    assert_eq!(input.mouse.cursor_mode(), CursorMode::Normal);
+    assert!(input.mouse.is_cursor_visible());
+    assert!(input.mouse.take_cursor_request().is_some());
+
+    input.mouse.grab_cursor();
+    input.handle(InputEvent::Focused(false));
+    assert_eq!(input.mouse.cursor_mode(), CursorMode::Normal);
+}
@@ -0,0 +1,27 @@
+use engine::glam::Vec2;
+use engine::graphics::{InterpolatedTransform, Transform2D};
+use std::f32::consts::PI;
+
+#[test]
+fn interpolates_between_ticks() {
+    let mut transform = InterpolatedTransform::new(Transform2D::from_position(Vec2::ZERO));
+    transform.snapshot();
+    transform.current.position = Vec2::new(10.0, -4.0);
+    transform.current.rotation = 0.5;
+
+    let halfway = transform.interpolate(0.5);
+    assert_eq!(halfway.position, Vec2::new(5.0, -2.0));
+    assert_eq!(halfway.rotation, 0.25);
+    assert_eq!(transform.interpolate(1.0), transform.current);
+
+    transform.teleport(Transform2D::from_position(Vec2::splat(100.0)));
+    assert_eq!(transform.interpolate(0.5).position, Vec2::splat(100.0));
+}
+
+#[test]
+fn rotation_takes_the_short_way_round() {
+    let from = Transform2D::IDENTITY.with_rotation(PI - 0.1);
+    let to = Transform2D::IDENTITY.with_rotation(-PI + 0.1);
+    let halfway = from.lerp(&to, 0.5);
+    assert!((halfway.rotation - PI).abs() < 1e-5);
+}
@@ -0,0 +1,84 @@
+#[allow(dead_code)]
+mod common;
+
+use common::headless_renderer;
+use engine::core::EngineError;
+use engine::glam::Vec2;
+use engine::graphics::{Camera2D, Color, Renderer};
+use winit::dpi::PhysicalSize;
+
+#[test]
+fn invalid_textures_are_rejected() {
+    let Some(renderer) = headless_renderer("invalid_textures_are_rejected", 16, 16) else {
+        return;
+    };
+
+    let short = renderer.create_texture(2, 2, &[255; 12]);
+    assert!(matches!(short, Err(EngineError::Asset(_))));
+    let empty = renderer.create_texture(0, 4, &[]);
+    assert!(matches!(empty, Err(EngineError::Asset(_))));
+    let huge = renderer.create_texture(u32::MAX, 1, &[]);
+    assert!(matches!(huge, Err(EngineError::Asset(_))));
+    let garbage = renderer.load_texture_from_bytes(b"not an image");
+    assert!(matches!(garbage, Err(EngineError::Asset(_))));
+
+    assert!(renderer.create_texture(1, 1, &[255; 4]).is_ok());
+}
+
+#[test]
+fn clear_discards_earlier_draw_calls() {
+    let Some(mut renderer) = headless_renderer("clear_discards_earlier_draw_calls", 16, 16) else {
+        return;
+    };
+
+    let mut frame = renderer.begin_frame().expect("offscreen frame");
+    frame.draw_rectangle(4.0, 4.0, Color::RED);
+    frame.draw_rectangle(4.0, 4.0, Color::RED);
+    frame.clear(Color::BLACK);
+    frame.draw_triangle(4.0, Color::GREEN);
+    frame.present();
+
+    let stats = renderer.stats();
+    assert_eq!(stats.draw_calls, 1);
+    assert_eq!(stats.vertices, 3);
+}
+
+#[test]
+fn zero_sizes_do_not_reach_wgpu() {
+    let empty = pollster::block_on(Renderer::new_headless(0, 16));
+    assert!(matches!(empty, Err(EngineError::Config(_))));
+
+    let Some(mut renderer) = headless_renderer("zero_sizes_do_not_reach_wgpu", 16, 16) else {
+        return;
+    };
+    renderer.resize(PhysicalSize::new(0, 0));
+    assert_eq!(renderer.size(), PhysicalSize::new(16, 16));
+    let frame = renderer.begin_frame().expect("offscreen frame");
+    frame.present();
+}
+
+#[test]
+fn camera_with_an_empty_viewport_stays_finite() {
+    let camera = Camera2D::new(Vec2::ZERO);
+    assert!(camera.view_projection().is_finite());
+}
+
+#[test]
+fn camera_with_zero_zoom_stays_finite() {
+    let mut camera = Camera2D::new(Vec2::new(800.0, 600.0));
+    camera.zoom = 0.0;
+    assert!(camera.view_projection().is_finite());
+    assert!(camera.screen_to_world(Vec2::new(10.0, 20.0)).is_finite());
+}
+
+#[test]
+fn screen_and_world_conversions_round_trip() {
+    let mut camera = Camera2D::new(Vec2::new(800.0, 600.0));
+    camera.position = Vec2::new(120.0, -45.0);
+    camera.rotation = 0.7;
+    camera.zoom = 2.5;
+
+    let world = Vec2::new(-30.0, 75.0);
+    let back = camera.screen_to_world(camera.world_to_screen(world));
+    assert!(back.abs_diff_eq(world, 1e-3), "{back} != {world}");
+}
@@ -0,0 +1,82 @@
+use engine::core::Time;
+use std::time::Duration;
+
+fn ticks_this_frame(time: &mut Time, real_delta: Duration) -> u32 {
+    time.advance(real_delta);
+    let mut ticks = 0;
+    while time.next_tick() {
+        assert_eq!(time.delta(), time.fixed_step());
+        ticks += 1;
+    }
+    ticks
+}
+
+#[test]
+fn scale_pause_and_single_step() {
+    let mut time = Time::new(10.0);
+    assert_eq!(ticks_this_frame(&mut time, Duration::from_millis(250)), 2);
+    assert_eq!(time.tick(), 2);
+    assert!((time.alpha() - 0.5).abs() < 1e-5);
+
+    time.set_time_scale(0.5);
+    assert_eq!(ticks_this_frame(&mut time, Duration::from_millis(300)), 2);
+    assert_eq!(time.delta(), Duration::from_millis(150));
+    assert_eq!(time.elapsed(), Duration::from_millis(400));
+    assert_eq!(time.real_elapsed(), Duration::from_millis(550));
+
+    time.pause();
+    assert_eq!(ticks_this_frame(&mut time, Duration::from_secs(1)), 0);
+    assert_eq!(time.delta(), Duration::ZERO);
+    time.step();
+    assert_eq!(ticks_this_frame(&mut time, Duration::from_secs(1)), 1);
+    assert_eq!(time.frame(), 3);
+    assert_eq!(time.tick(), 5);
+}
+
+#[test]
+fn catch_up_is_capped_after_a_stall() {
+    let mut time = Time::new(60.0);
+    time.set_max_catch_up_steps(3);
+    assert_eq!(ticks_this_frame(&mut time, Duration::from_secs(2)), 3);
+    assert!(time.alpha() < 1.0);
+    assert_eq!(ticks_this_frame(&mut time, Duration::ZERO), 0);
+}
+
+#[test]
+fn invalid_tick_rates_are_clamped() {
+    let mut time = Time::new(0.0);
+    assert_eq!(time.tick_rate(), 1.0);
+    time.set_tick_rate(-30.0);
+    assert_eq!(time.tick_rate(), 1.0);
+    time.set_tick_rate(f64::NAN);
+    assert!((time.tick_rate() - 60.0).abs() < 1e-3);
+    time.set_tick_rate(f64::INFINITY);
+    assert!((time.tick_rate() - 10_000.0).abs() < 1e-3);
+}
+
+#[test]
+fn idle_time_is_not_simulated_after_a_clock_reset() {
+    let mut time = Time::new(60.0);
+    time.begin_frame();
+    std::thread::sleep(Duration::from_millis(50));
+    time.reset_frame_clock();
+    time.begin_frame();
+    assert_eq!(time.real_delta(), Duration::ZERO);
+    assert!(!time.next_tick());
+}
+
+#[test]
+fn invalid_time_scales_are_clamped() {
+    let mut time = Time::new(60.0);
+    time.set_time_scale(f64::INFINITY);
+    assert_eq!(time.time_scale(), 1_000.0);
+    time.set_time_scale(1e30);
+    assert_eq!(time.time_scale(), 1_000.0);
+    time.advance(Duration::from_secs(1));
+    assert_eq!(time.delta(), Duration::from_secs(1_000));
+
+    time.set_time_scale(f64::NAN);
+    assert_eq!(time.time_scale(), 1_000.0);
+    time.set_time_scale(-2.0);
+    assert_eq!(time.time_scale(), 0.0);
+}